@@ -0,0 +1,263 @@
+use std::vec::Vec;
+use std::sync::atomic::{AtomicU64, Ordering};
+use crate::bitvectors::{Bitvector, Bit};
+use crate::int_vector::IntVector;
+use crate::structure::StructureNode;
+
+#[cfg(test)]
+mod tests;
+
+/// Thread-safe version of `IntVector`, where the raw data is stored into `AtomicU64` words.
+/// Values can be set through shared reference, so several threads can fill the vector at the
+/// same time. Each word is updated with compare-and-swap, such that setting a value never
+/// overwrites the bits of the other values in the same word.
+///
+/// Note that a value spanning two words is updated with two separate operations, so concurrent
+/// writes into the *same* value can mix the bits of both writes.
+///
+/// ```
+/// use std::thread;
+/// use halko_rust::atomic_vectors::AtomicIntVector;
+///
+/// let aiv = AtomicIntVector::new(100, 7);
+///
+/// thread::scope(|s| {
+///     for t in 0..4 {
+///         let aiv = &aiv;
+///         s.spawn(move || {
+///             for i in (t..100).step_by(4) { aiv.set(i, i as u64); }
+///         });
+///     }
+/// });
+///
+/// let iv = aiv.into_inner();
+/// for i in 0..100 { assert_eq!(iv.get(i), i as u64); }
+/// ```
+#[derive(Debug)]
+pub struct AtomicIntVector {
+    l: usize, // size of max value in bits
+    data: Vec<AtomicU64>,
+    n: usize,
+}
+
+impl AtomicIntVector {
+    /// Create new `AtomicIntVector` of size `n`, where each value is at most `2^l-1`
+    pub fn new(n: usize, l: usize) -> AtomicIntVector {
+        AtomicIntVector::from(IntVector::new(n, l))
+    }
+
+    /// Sets new value `new_val` into the `i`-th value.
+    pub fn set(&self, i: usize, new_val: u64) {
+        if self.l < 64 && new_val >> self.l != 0 {
+            panic!("[AtomicIntVector::set], Error setting new value into AtomicIntVector, new value is too large. \
+                   new value: {}, largest valid value: {}.",
+                   new_val, (1_u64 << self.l)-1);
+        }
+        if i >= self.n {
+            panic!("[AtomicIntVector::set], Index out of bounds, i:{}, AtomicIntVector length:{}",
+                   i, self.n);
+        }
+
+        let k = (i*self.l)/64;
+        let loc_i = (i*self.l)%64;
+
+        if loc_i + self.l <= 64 {
+            let mask = if self.l == 64 {u64::MAX} else {!(u64::MAX << self.l) << loc_i};
+            update_bits(&self.data[k], mask, new_val << loc_i);
+        } else {
+            update_bits(&self.data[k], u64::MAX << loc_i, new_val << loc_i);
+            let rest = loc_i + self.l - 64;
+            update_bits(&self.data[k+1], !(u64::MAX << rest), new_val >> (64-loc_i));
+        }
+    }
+
+    /// Returns the `i`-th value.
+    pub fn get(&self, i: usize) -> u64 {
+        if i >= self.n {
+            panic!("[AtomicIntVector::get], Index out of bounds, i:{}, AtomicIntVector length:{}",
+                   i, self.n);
+        }
+
+        let k = (i*self.l)/64;
+        let loc_i = (i*self.l)%64;
+        let mut res = self.data[k].load(Ordering::Acquire) >> loc_i;
+        if loc_i + self.l > 64 {
+            res |= self.data[k+1].load(Ordering::Acquire) << (64-loc_i);
+        }
+        if self.l == 64 {res} else {res & !(u64::MAX << self.l)}
+    }
+
+    /// Returns the length of the AtomicIntVector.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    /// Returns `true` if the AtomicIntVector does not contain any values.
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Returns the size of the vector in bytes: the data words, the length and the width.
+    pub fn size_in_bytes(&self) -> usize {
+        8*self.data.len() + 16
+    }
+
+    /// Returns the space breakdown of the vector.
+    pub fn structure_tree(&self) -> StructureNode {
+        StructureNode::new("AtomicIntVector", self.size_in_bytes())
+            .with_field("l", self.l as u64)
+            .with_field("n", self.n as u64)
+    }
+
+    /// Converts the AtomicIntVector into plain `IntVector`.
+    pub fn into_inner(self) -> IntVector {
+        let data = self.data.into_iter().map(|x| x.into_inner()).collect();
+        IntVector::from_raw_parts(data, self.n, self.l)
+    }
+}
+
+impl From<IntVector> for AtomicIntVector {
+    fn from(iv: IntVector) -> AtomicIntVector {
+        AtomicIntVector {
+            l: iv.get_l(),
+            data: iv.get_data().iter().map(|x| AtomicU64::new(*x)).collect(),
+            n: iv.len(),
+        }
+    }
+}
+
+/// Thread-safe version of `Bitvector`, where the raw data is stored into `AtomicU64` words.
+/// Bits are set with atomic `fetch_set` and `fetch_clear`, so several threads can set any bits
+/// at the same time.
+///
+/// ```
+/// use std::thread;
+/// use halko_rust::bitvectors::Bit;
+/// use halko_rust::atomic_vectors::AtomicBitvector;
+///
+/// let abv = AtomicBitvector::new(1000);
+///
+/// thread::scope(|s| {
+///     for t in 0..4 {
+///         let abv = &abv;
+///         s.spawn(move || {
+///             for i in (t..1000).step_by(4) {
+///                 if i % 3 == 0 { abv.set(i, Bit::ONE); }
+///             }
+///         });
+///     }
+/// });
+///
+/// let bv = abv.into_inner();
+/// for i in 0..1000 { assert_eq!(bv.get(i), (i % 3 == 0) as u32); }
+/// ```
+#[derive(Debug)]
+pub struct AtomicBitvector {
+    data: Vec<AtomicU64>,
+    n: usize,
+}
+
+impl AtomicBitvector {
+    /// Builds atomic bitvector of length `n` containing only 0s.
+    pub fn new(n: usize) -> AtomicBitvector {
+        AtomicBitvector::from(Bitvector::build_empty(n))
+    }
+
+    /// Sets or unsets the i-th bit. Returns the previous value of the bit.
+    pub fn set(&self, i: usize, val: Bit) -> u32 {
+        if i >= self.n {
+            panic!("[AtomicBitvector::set], Index out of bounds, i:{}, length of bitvector:{}", i, self.n);
+        }
+        match val {
+            Bit::ZERO => self.fetch_clear(i),
+            Bit::ONE => self.fetch_set(i),
+        }
+    }
+
+    /// Returns bit value in the i-th bit.
+    pub fn get(&self, i: usize) -> u32 {
+        if i >= self.n {
+            panic!("[AtomicBitvector::get], Index out of bounds, i:{}, length of bitvector:{}", i, self.n);
+        }
+        ((self.data[i/64].load(Ordering::Acquire) >> (i%64)) & 1) as u32
+    }
+
+    /// Sets the i-th bit to 1 with atomic `fetch_or`. Returns the previous value of the bit.
+    ///
+    /// ```
+    /// use halko_rust::atomic_vectors::AtomicBitvector;
+    ///
+    /// let abv = AtomicBitvector::new(100);
+    ///
+    /// assert_eq!(abv.fetch_set(99), 0);
+    /// assert_eq!(abv.fetch_set(99), 1);
+    /// assert_eq!(abv.fetch_clear(99), 1);
+    /// assert_eq!(abv.get(99), 0);
+    /// ```
+    pub fn fetch_set(&self, i: usize) -> u32 {
+        if i >= self.n {
+            panic!("[AtomicBitvector::fetch_set], Index out of bounds, i:{}, length of bitvector:{}", i, self.n);
+        }
+        const I: u64 = 1;
+        let prev = self.data[i/64].fetch_or(I << (i%64), Ordering::AcqRel);
+        ((prev >> (i%64)) & 1) as u32
+    }
+
+    /// Sets the i-th bit to 0 with atomic `fetch_and`. Returns the previous value of the bit.
+    pub fn fetch_clear(&self, i: usize) -> u32 {
+        if i >= self.n {
+            panic!("[AtomicBitvector::fetch_clear], Index out of bounds, i:{}, length of bitvector:{}", i, self.n);
+        }
+        const I: u64 = 1;
+        let prev = self.data[i/64].fetch_and(!(I << (i%64)), Ordering::AcqRel);
+        ((prev >> (i%64)) & 1) as u32
+    }
+
+    /// Return length of the bitvector (number of bits).
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    /// Returns `true` if the bitvector does not contain any bits.
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Returns the size of the bitvector in bytes: the data words and the length.
+    pub fn size_in_bytes(&self) -> usize {
+        8*self.data.len() + 8
+    }
+
+    /// Returns the space breakdown of the bitvector.
+    pub fn structure_tree(&self) -> StructureNode {
+        StructureNode::new("AtomicBitvector", self.size_in_bytes())
+            .with_field("n", self.n as u64)
+    }
+
+    /// Converts the AtomicBitvector into plain `Bitvector`.
+    pub fn into_inner(self) -> Bitvector {
+        let data = self.data.into_iter().map(|x| x.into_inner()).collect();
+        Bitvector::from_raw_parts(data, self.n)
+    }
+}
+
+impl From<Bitvector> for AtomicBitvector {
+    fn from(bv: Bitvector) -> AtomicBitvector {
+        AtomicBitvector {
+            data: bv.get_data().iter().map(|x| AtomicU64::new(*x)).collect(),
+            n: bv.len(),
+        }
+    }
+}
+
+/// Replaces the bits of `word` selected by `mask` with the bits of `val` using compare-and-swap.
+fn update_bits(word: &AtomicU64, mask: u64, val: u64) {
+    let mut current = word.load(Ordering::Acquire);
+    loop {
+        let new = (current & !mask) | (val & mask);
+        match word.compare_exchange_weak(current, new, Ordering::AcqRel, Ordering::Acquire) {
+            Ok(_) => return,
+            Err(x) => current = x,
+        }
+    }
+}
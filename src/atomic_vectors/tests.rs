@@ -0,0 +1,156 @@
+use std::vec::Vec;
+use std::thread;
+use rand::{Rng, thread_rng};
+
+use crate::atomic_vectors::{AtomicIntVector, AtomicBitvector};
+use crate::bitvectors::{Bitvector, Bit};
+use crate::int_vector::IntVector;
+
+// fn: new, into_inner
+#[test]
+fn new_into_inner_equals_int_vector() {
+    for l in 1..=64 {
+        let aiv = AtomicIntVector::new(13, l);
+        assert_eq!(aiv.into_inner(), IntVector::new(13, l));
+    }
+}
+
+// fn: set, get
+#[test]
+fn set_get_single_thread_all_widths() {
+    let mut rng = thread_rng();
+    for l in 1..=64 {
+        let aiv = AtomicIntVector::new(100, l);
+        let mut iv = IntVector::new(100, l);
+        for _ in 0..300 {
+            let i = rng.gen_range(0..100);
+            let x = if l == 64 {rng.gen()} else {rng.gen_range(0..1 << l)};
+            aiv.set(i, x);
+            iv.set(i, x);
+        }
+        for i in 0..100 { assert_eq!(aiv.get(i), iv.get(i)); }
+        assert_eq!(aiv.into_inner(), iv);
+    }
+}
+
+// fn: set
+#[test]
+#[should_panic]
+fn set_too_large_value() {
+    let aiv = AtomicIntVector::new(10, 3);
+    aiv.set(0, 8);
+}
+
+// fn: set
+#[test]
+#[should_panic]
+fn set_index_out_of_bounds() {
+    let aiv = AtomicIntVector::new(10, 3);
+    aiv.set(10, 1);
+}
+
+// fn: set
+#[test]
+fn set_parallel_interleaved_values() {
+    // neighbouring values are written by different threads, so the words are shared
+    for l in [1, 3, 7, 13, 33, 64] {
+        let n = 10_000;
+        let aiv = AtomicIntVector::new(n, l);
+        let mask = if l == 64 {u64::MAX} else {!(u64::MAX << l)};
+        thread::scope(|s| {
+            for t in 0..8 {
+                let aiv = &aiv;
+                s.spawn(move || {
+                    for i in (t..n).step_by(8) {
+                        aiv.set(i, (i as u64 * 7919) & mask);
+                    }
+                });
+            }
+        });
+        let iv = aiv.into_inner();
+        for i in 0..n { assert_eq!(iv.get(i), (i as u64 * 7919) & mask, "l:{}, i:{}", l, i); }
+    }
+}
+
+// fn: from
+#[test]
+fn from_int_vector_keeps_values() {
+    let mut iv = IntVector::new(20, 9);
+    for i in 0..20 { iv.set(i, 500 - i as u64); }
+    let aiv = AtomicIntVector::from(iv.clone());
+    for i in 0..20 { assert_eq!(aiv.get(i), iv.get(i)); }
+}
+
+// fn: set, get
+#[test]
+fn bitvector_set_returns_previous_bit() {
+    let abv = AtomicBitvector::new(10);
+    assert_eq!(abv.set(3, Bit::ONE), 0);
+    assert_eq!(abv.set(3, Bit::ONE), 1);
+    assert_eq!(abv.get(3), 1);
+    assert_eq!(abv.set(3, Bit::ZERO), 1);
+    assert_eq!(abv.get(3), 0);
+}
+
+// fn: fetch_set, fetch_clear
+#[test]
+fn bitvector_fetch_set_clear_last_word() {
+    // the last word holds only 6 bits
+    let abv = AtomicBitvector::new(70);
+    assert_eq!(abv.fetch_set(69), 0);
+    assert_eq!(abv.fetch_set(64), 0);
+    assert_eq!(abv.fetch_set(69), 1);
+    assert_eq!(abv.fetch_clear(64), 1);
+    assert_eq!(abv.fetch_clear(64), 0);
+    let bv = abv.into_inner();
+    assert_eq!(bv[1], 1 << 5);
+    assert_eq!(bv.count_ones_in_words(0..2), 1);
+}
+
+// fn: fetch_set
+#[test]
+#[should_panic]
+fn bitvector_fetch_set_padding_bit() {
+    let abv = AtomicBitvector::new(70);
+    abv.fetch_set(70);
+}
+
+// fn: fetch_clear
+#[test]
+#[should_panic]
+fn bitvector_fetch_clear_out_of_bounds() {
+    let abv = AtomicBitvector::new(70);
+    abv.fetch_clear(128);
+}
+
+// fn: set, into_inner
+#[test]
+fn bitvector_parallel_overlapping_bits() {
+    let n = 5000;
+    let abv = AtomicBitvector::new(n);
+    // every thread sets all bits divisible by its own number
+    thread::scope(|s| {
+        for t in 2..8 {
+            let abv = &abv;
+            s.spawn(move || {
+                for i in (0..n).step_by(t) { abv.set(i, Bit::ONE); }
+            });
+        }
+    });
+    let bv = abv.into_inner();
+    for i in 0..n {
+        let expected = (2..8).any(|t| i % t == 0) as u32;
+        assert_eq!(bv.get(i), expected);
+    }
+}
+
+// fn: from, into_inner
+#[test]
+fn bitvector_round_trip() {
+    let mut rng = thread_rng();
+    let a: Vec<u32> = (0..300).map(|_| rng.gen_range(0..=1)).collect();
+    let abv = AtomicBitvector::from(Bitvector::build(&a));
+    assert_eq!(abv.len(), 300);
+    let bv = abv.into_inner();
+    for (i, x) in a.iter().enumerate() { assert_eq!(bv.get(i), *x); }
+}
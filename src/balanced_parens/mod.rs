@@ -0,0 +1,362 @@
+use std::vec::Vec;
+use crate::bitvectors::Bitvector;
+use crate::int_vector::{IntVector, bit_length};
+use crate::rank_support::RankSupport;
+use crate::structure::StructureNode;
+
+#[cfg(test)]
+mod tests;
+
+const BLOCK: usize = 64; // bits per leaf of the range min-max tree
+
+/// Balanced parentheses sequence, where `(` is 1bit and `)` is 0bit, with navigation by the
+/// excess values. The excess of position `i` is the number of `(` minus the number of `)` in
+/// range `[0,i]`.
+///
+/// Searches use a range min-max tree: a complete binary tree in heap order over blocks of 64
+/// bits, where each node stores the minimum and the maximum excess of its range. Since the
+/// excess changes by one at each position, a node contains an excess value if and only if the
+/// value is between its minimum and maximum. All operations take O(log n) time.
+///
+/// ```
+/// use halko_rust::bitvectors::Bitvector;
+/// use halko_rust::balanced_parens::BalancedParens;
+///
+/// // (()(()))
+/// let bp = BalancedParens::new(Bitvector::build(&[1,1,0,1,1,0,0,0]));
+///
+/// assert_eq!(bp.find_close(0), 7);
+/// assert_eq!(bp.find_close(3), 6);
+/// assert_eq!(bp.find_open(5), 4);
+/// assert_eq!(bp.enclose(4), Some(3));
+/// assert_eq!(bp.enclose(0), None);
+/// assert_eq!(bp.excess(4), 3);
+/// ```
+pub struct BalancedParens {
+    bits: RankSupport,
+    min_excess: IntVector, // minimum excess of each node, shifted by n
+    max_excess: IntVector, // maximum excess of each node, shifted by n
+    leaves: usize,
+    n: usize,
+}
+
+impl BalancedParens {
+    /// Builds navigation structure for the parentheses `bv`.
+    pub fn new(bv: Bitvector) -> BalancedParens {
+        let n = bv.len();
+        let blocks = n.div_ceil(BLOCK).max(1);
+        let leaves = blocks.next_power_of_two();
+        let l = bit_length(2*n as u64 + 1);
+
+        // padding leaves have empty range of excess values, min > max
+        let mut min_excess = IntVector::new(2*leaves, l);
+        let mut max_excess = IntVector::new(2*leaves, l);
+        let mut excess = n as u64;
+        for k in 0..leaves {
+            let (mut min, mut max) = (2*n as u64 + 1, 0);
+            for p in k*BLOCK..n.min((k+1)*BLOCK) {
+                if bv.get(p) == 1 {excess += 1} else {excess -= 1}
+                min = min.min(excess);
+                max = max.max(excess);
+            }
+            min_excess.set(leaves + k, min);
+            max_excess.set(leaves + k, max);
+        }
+        for v in (1..leaves).rev() {
+            min_excess.set(v, min_excess.get(2*v).min(min_excess.get(2*v+1)));
+            max_excess.set(v, max_excess.get(2*v).max(max_excess.get(2*v+1)));
+        }
+
+        BalancedParens {
+            bits: RankSupport::new(bv),
+            min_excess,
+            max_excess,
+            leaves,
+            n,
+        }
+    }
+
+    /// Returns the length of the sequence.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    pub fn get_bv(&self) -> &Bitvector {
+        self.bits.get_bv()
+    }
+
+    /// Returns the rank support of the parentheses.
+    pub fn get_rank_support(&self) -> &RankSupport {
+        &self.bits
+    }
+
+    /// Returns true if the `i`-th parenthesis is `(`.
+    pub fn is_open(&self, i: usize) -> bool {
+        self.get_bv().get(i) == 1
+    }
+
+    /// Returns the excess of range `[0,i]`.
+    pub fn excess(&self, i: usize) -> i64 {
+        if i >= self.n {
+            panic!("[BalancedParens::excess], Index out of bounds, i:{}, BalancedParens length:{}", i, self.n);
+        }
+        2*self.bits.rank1(i) as i64 - (i as i64 + 1)
+    }
+
+    /// Returns the smallest `j>i` with `excess(j) = excess(i)+d`, or `None` if there is not any.
+    ///
+    /// ```
+    /// use halko_rust::bitvectors::Bitvector;
+    /// use halko_rust::balanced_parens::BalancedParens;
+    ///
+    /// // excess: 1,2,1,2,3,2,1,0
+    /// let bp = BalancedParens::new(Bitvector::build(&[1,1,0,1,1,0,0,0]));
+    ///
+    /// assert_eq!(bp.fwd_search(1, -1), Some(2));
+    /// assert_eq!(bp.fwd_search(1, 1), Some(4));
+    /// assert_eq!(bp.fwd_search(0, -1), Some(7));
+    /// assert_eq!(bp.fwd_search(0, 3), None);
+    /// ```
+    pub fn fwd_search(&self, i: usize, d: i64) -> Option<usize> {
+        let target = self.excess(i) + d;
+
+        let k = i/BLOCK;
+        if let Some(j) = self.scan_fwd(i+1, self.block_end(k), self.excess(i), target) {
+            return Some(j);
+        }
+
+        // go up until a right sibling contains the target, then down to its leftmost leaf
+        let mut v = self.leaves + k;
+        while v > 1 {
+            if v.is_multiple_of(2) && self.contains(v+1, target) {
+                v += 1;
+                while v < self.leaves {
+                    v = if self.contains(2*v, target) {2*v} else {2*v+1};
+                }
+                let k = v - self.leaves;
+                return self.scan_fwd(k*BLOCK, self.block_end(k), self.excess_before(k*BLOCK), target);
+            }
+            v /= 2;
+        }
+        None
+    }
+
+    /// Returns the largest `j<i` with `excess(j) = excess(i)+d`, or `None` if there is not any.
+    /// The position before the sequence with excess 0 is not considered.
+    ///
+    /// ```
+    /// use halko_rust::bitvectors::Bitvector;
+    /// use halko_rust::balanced_parens::BalancedParens;
+    ///
+    /// // excess: 1,2,1,2,3,2,1,0
+    /// let bp = BalancedParens::new(Bitvector::build(&[1,1,0,1,1,0,0,0]));
+    ///
+    /// assert_eq!(bp.bwd_search(5, 0), Some(3));
+    /// assert_eq!(bp.bwd_search(6, 1), Some(5));
+    /// assert_eq!(bp.bwd_search(7, 1), Some(6));
+    /// assert_eq!(bp.bwd_search(2, -1), None);
+    /// ```
+    pub fn bwd_search(&self, i: usize, d: i64) -> Option<usize> {
+        let target = self.excess(i) + d;
+        if i == 0 {
+            return None;
+        }
+
+        let k = (i-1)/BLOCK;
+        if let Some(j) = self.scan_bwd(k*BLOCK, i-1, target) {
+            return Some(j);
+        }
+
+        // go up until a left sibling contains the target, then down to its rightmost leaf
+        let mut v = self.leaves + k;
+        while v > 1 {
+            if v % 2 == 1 && self.contains(v-1, target) {
+                v -= 1;
+                while v < self.leaves {
+                    v = if self.contains(2*v+1, target) {2*v+1} else {2*v};
+                }
+                let k = v - self.leaves;
+                return self.scan_bwd(k*BLOCK, self.block_end(k), target);
+            }
+            v /= 2;
+        }
+        None
+    }
+
+    /// Returns the position of `)` matching the `(` in position `i`.
+    /// Function panics if the parenthesis is not `(` or if it does not have a match.
+    pub fn find_close(&self, i: usize) -> usize {
+        if !self.is_open(i) {
+            panic!("[BalancedParens::find_close], Parenthesis is not open, i:{}", i);
+        }
+        match self.fwd_search(i, -1) {
+            Some(j) => j,
+            None => panic!("[BalancedParens::find_close], Parenthesis does not have a match, i:{}", i),
+        }
+    }
+
+    /// Returns the position of `(` matching the `)` in position `i`.
+    /// Function panics if the parenthesis is not `)` or if it does not have a match.
+    pub fn find_open(&self, i: usize) -> usize {
+        if self.is_open(i) || self.excess(i) < 0 {
+            panic!("[BalancedParens::find_open], Parenthesis is not closed or does not have a match, i:{}", i);
+        }
+        match self.bwd_search(i, 0) {
+            Some(j) => j+1,
+            None => 0,
+        }
+    }
+
+    /// Returns the position of `(` of the closest pair enclosing the `(` in position `i`, or
+    /// `None` if the pair of `i` is not enclosed.
+    pub fn enclose(&self, i: usize) -> Option<usize> {
+        if !self.is_open(i) {
+            panic!("[BalancedParens::enclose], Parenthesis is not open, i:{}", i);
+        }
+        if self.excess(i) <= 1 {
+            return None;
+        }
+        match self.bwd_search(i, -2) {
+            Some(j) => Some(j+1),
+            None => Some(0),
+        }
+    }
+
+    /// Returns the leftmost position of the minimum excess in range `[a,b]`.
+    ///
+    /// ```
+    /// use halko_rust::bitvectors::Bitvector;
+    /// use halko_rust::balanced_parens::BalancedParens;
+    ///
+    /// // excess: 1,2,1,2,3,2,1,0
+    /// let bp = BalancedParens::new(Bitvector::build(&[1,1,0,1,1,0,0,0]));
+    ///
+    /// assert_eq!(bp.rmq(1, 5), 2);
+    /// assert_eq!(bp.rmq(3, 5), 3);
+    /// assert_eq!(bp.rmq(0, 7), 7);
+    /// ```
+    pub fn rmq(&self, a: usize, b: usize) -> usize {
+        if a > b || b >= self.n {
+            panic!("[BalancedParens::rmq], Invalid range, a:{}, b:{}, BalancedParens length:{}", a, b, self.n);
+        }
+        let (ka, kb) = (a/BLOCK, b/BLOCK);
+        if ka == kb {
+            return self.scan_min(a, b).1;
+        }
+
+        // candidates from left to right: the block of a, the nodes covering the blocks between,
+        // and the block of b
+        let mut best = self.scan_min(a, self.block_end(ka));
+        let mut best_node = None;
+        let mut nodes = Vec::new();
+        let mut right_nodes = Vec::new();
+        let (mut left, mut right) = (self.leaves + ka + 1, self.leaves + kb);
+        while left < right {
+            if left % 2 == 1 {
+                nodes.push(left);
+                left += 1;
+            }
+            if right % 2 == 1 {
+                right -= 1;
+                right_nodes.push(right);
+            }
+            left /= 2;
+            right /= 2;
+        }
+        nodes.extend(right_nodes.iter().rev());
+        for v in nodes {
+            if self.node_min(v) < best.0 {
+                best.0 = self.node_min(v);
+                best_node = Some(v);
+            }
+        }
+        if let Some(mut v) = best_node {
+            while v < self.leaves {
+                v = if self.node_min(2*v) == best.0 {2*v} else {2*v+1};
+            }
+            let k = v - self.leaves;
+            best = self.scan_min(k*BLOCK, self.block_end(k));
+        }
+
+        let last = self.scan_min(kb*BLOCK, b);
+        if last.0 < best.0 {
+            best = last;
+        }
+        best.1
+    }
+
+    /// Returns the size of the structure in bytes.
+    pub fn size_in_bytes(&self) -> usize {
+        self.bits.size_in_bytes() + self.min_excess.size_in_bytes() + self.max_excess.size_in_bytes() + 16
+    }
+
+    /// Returns the space breakdown of the structure.
+    pub fn structure_tree(&self) -> StructureNode {
+        StructureNode::new("BalancedParens", self.size_in_bytes())
+            .with_field("n", self.n as u64)
+            .with_child(self.bits.structure_tree().with_name("bits"))
+            .with_child(self.min_excess.structure_tree().with_name("min_excess"))
+            .with_child(self.max_excess.structure_tree().with_name("max_excess"))
+    }
+
+    fn node_min(&self, v: usize) -> i64 {
+        self.min_excess.get(v) as i64 - self.n as i64
+    }
+
+    fn contains(&self, v: usize, target: i64) -> bool {
+        let min = self.min_excess.get(v) as i64 - self.n as i64;
+        let max = self.max_excess.get(v) as i64 - self.n as i64;
+        min <= target && target <= max
+    }
+
+    // Last position of the k-th block.
+    fn block_end(&self, k: usize) -> usize {
+        self.n.min((k+1)*BLOCK) - 1
+    }
+
+    // Excess of range [0,p), which is 0 for p=0.
+    fn excess_before(&self, p: usize) -> i64 {
+        if p == 0 {0} else {self.excess(p-1)}
+    }
+
+    // First position in [from,to] with excess `target`, when the excess before `from` is `e`.
+    fn scan_fwd(&self, from: usize, to: usize, e: i64, target: i64) -> Option<usize> {
+        let mut e = e;
+        for p in from..=to {
+            e += if self.is_open(p) {1} else {-1};
+            if e == target {
+                return Some(p);
+            }
+        }
+        None
+    }
+
+    // Last position in [from,to] with excess `target`.
+    fn scan_bwd(&self, from: usize, to: usize, target: i64) -> Option<usize> {
+        let mut e = self.excess(to);
+        for p in (from..=to).rev() {
+            if e == target {
+                return Some(p);
+            }
+            e -= if self.is_open(p) {1} else {-1};
+        }
+        None
+    }
+
+    // Returns (minimum excess, leftmost position of the minimum) in range [from,to].
+    fn scan_min(&self, from: usize, to: usize) -> (i64, usize) {
+        let mut e = self.excess(from);
+        let mut best = (e, from);
+        for p in from+1..=to {
+            e += if self.is_open(p) {1} else {-1};
+            if e < best.0 {
+                best = (e, p);
+            }
+        }
+        best
+    }
+}
@@ -0,0 +1,148 @@
+use std::vec::Vec;
+use rand::{Rng, thread_rng};
+
+use crate::balanced_parens::BalancedParens;
+use crate::bitvectors::Bitvector;
+
+// random balanced sequence of n pairs
+fn random_balanced(n: usize) -> Vec<u32> {
+    let mut rng = thread_rng();
+    let mut res = Vec::with_capacity(2*n);
+    let (mut open, mut depth) = (0, 0);
+    while res.len() < 2*n {
+        if open < n && (depth == 0 || rng.gen_bool(0.5)) {
+            res.push(1);
+            open += 1;
+            depth += 1;
+        } else {
+            res.push(0);
+            depth -= 1;
+        }
+    }
+    res
+}
+
+fn excesses(a: &[u32]) -> Vec<i64> {
+    let mut e = 0;
+    a.iter().map(|x| {e += if *x == 1 {1} else {-1}; e}).collect()
+}
+
+// matching parenthesis and enclosing open parenthesis of each position
+fn naive_matches(a: &[u32]) -> (Vec<usize>, Vec<Option<usize>>) {
+    let mut matches = vec![0; a.len()];
+    let mut enclose = vec![None; a.len()];
+    let mut stack: Vec<usize> = Vec::new();
+    for (i, x) in a.iter().enumerate() {
+        if *x == 1 {
+            enclose[i] = stack.last().copied();
+            stack.push(i);
+        } else {
+            let j = stack.pop().unwrap();
+            matches[i] = j;
+            matches[j] = i;
+        }
+    }
+    (matches, enclose)
+}
+
+// fn: new
+#[test]
+fn bp_new_excess() {
+    let a = random_balanced(1000);
+    let bp = BalancedParens::new(Bitvector::build(&a));
+    assert_eq!(bp.len(), 2000);
+    for (i, e) in excesses(&a).iter().enumerate() {
+        assert_eq!(bp.excess(i), *e);
+    }
+}
+
+// fn: find_close, find_open, enclose
+#[test]
+fn bp_navigation_random() {
+    for n in [1, 10, 100, 5000, 50_000] {
+        let a = random_balanced(n);
+        let bp = BalancedParens::new(Bitvector::build(&a));
+        let (matches, enclose) = naive_matches(&a);
+        for (i, x) in a.iter().enumerate() {
+            if *x == 1 {
+                assert_eq!(bp.find_close(i), matches[i]);
+                assert_eq!(bp.enclose(i), enclose[i]);
+            } else {
+                assert_eq!(bp.find_open(i), matches[i]);
+            }
+        }
+    }
+}
+
+// fn: find_close, enclose
+#[test]
+fn bp_navigation_deep() {
+    // ((((...)))) followed by ()()()...
+    let mut a = vec![1; 3000];
+    a.extend(vec![0; 3000]);
+    for _ in 0..3000 {
+        a.extend([1, 0]);
+    }
+    let bp = BalancedParens::new(Bitvector::build(&a));
+    let (matches, enclose) = naive_matches(&a);
+    for i in [0, 1, 63, 64, 1000, 2999, 6000, 9000, 11998] {
+        assert_eq!(bp.find_close(i), matches[i]);
+        assert_eq!(bp.enclose(i), enclose[i]);
+    }
+}
+
+// fn: find_close
+#[test]
+#[should_panic]
+fn bp_find_close_of_close() {
+    let bp = BalancedParens::new(Bitvector::build(&[1,0]));
+    bp.find_close(1);
+}
+
+// fn: fwd_search, bwd_search
+#[test]
+fn bp_search_random() {
+    let mut rng = thread_rng();
+    // any sequence, not only balanced
+    let a: Vec<u32> = (0..5000).map(|_| rng.gen_bool(0.5) as u32).collect();
+    let e = excesses(&a);
+    let bp = BalancedParens::new(Bitvector::build(&a));
+    for _ in 0..3000 {
+        let i = rng.gen_range(0..a.len());
+        let d = rng.gen_range(-20..20);
+        let target = e[i] + d;
+        let fwd = (i+1..a.len()).find(|j| e[*j] == target);
+        let bwd = (0..i).rev().find(|j| e[*j] == target);
+        assert_eq!(bp.fwd_search(i, d), fwd);
+        assert_eq!(bp.bwd_search(i, d), bwd);
+    }
+}
+
+// fn: rmq
+#[test]
+fn bp_rmq_random() {
+    let mut rng = thread_rng();
+    let a = random_balanced(20_000);
+    let e = excesses(&a);
+    let bp = BalancedParens::new(Bitvector::build(&a));
+    for _ in 0..3000 {
+        let x = rng.gen_range(0..a.len());
+        let y = if rng.gen_bool(0.5) {rng.gen_range(0..a.len())} else {(x + rng.gen_range(0..200)).min(a.len()-1)};
+        let (s, t) = (x.min(y), x.max(y));
+        let mut best = s;
+        for j in s..=t {
+            if e[j] < e[best] {
+                best = j;
+            }
+        }
+        assert_eq!(bp.rmq(s, t), best);
+    }
+}
+
+// fn: rmq
+#[test]
+#[should_panic]
+fn bp_rmq_invalid_range() {
+    let bp = BalancedParens::new(Bitvector::build(&[1,0]));
+    bp.rmq(0, 2);
+}
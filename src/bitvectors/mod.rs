@@ -1,5 +1,8 @@
 use std::vec::Vec;
-use std::ops::{Index, IndexMut};
+use std::ops::{Index, IndexMut, Range};
+use crate::structure::StructureNode;
+
+mod popcount;
 
 #[cfg(test)]
 mod tests;
@@ -109,7 +112,7 @@ impl Bitvector {
     /// // 00000000 00000000 00000000 00000000 00000000 00000000 00000000 00000000
     /// // 01000000 00000000 00000000 00000000 00000000 00000000 00000000 00000000
     /// ```
-    pub fn build_from_vec(v: &Vec<u64>) -> Bitvector {
+    pub fn build_from_vec(v: &[u64]) -> Bitvector {
         Bitvector {
             data: v.to_vec(),
             n: v.len()*64,
@@ -126,7 +129,7 @@ impl Bitvector {
     ///
     /// for i in 0..v.len() { assert_eq!(v[i], bv.get(i)); }
     /// ```
-    pub fn build_from_vec2(v: &Vec<u32>) -> Bitvector {
+    pub fn build_from_vec2(v: &[u32]) -> Bitvector {
         let mut bv = Bitvector::build_empty(v.len());
         for (i, val) in v.iter().enumerate() {
             match val {
@@ -140,6 +143,26 @@ impl Bitvector {
     }
 
 
+    /// Creates bitvector of length `n` from raw data, where `data` has at least `n/64+1` words.
+    pub(crate) fn from_raw_parts(data: Vec<u64>, n: usize) -> Bitvector {
+        Bitvector {
+            data,
+            n,
+        }
+    }
+
+    /// Returns reference to the raw data of the bitvector.
+    ///
+    /// ```
+    /// use halko_rust::bitvectors::Bitvector;
+    ///
+    /// let bv = Bitvector::build_empty(64);
+    /// assert_eq!(bv.get_data().len(), 2);
+    /// ```
+    pub fn get_data(&self) -> &Vec<u64> {
+        &self.data
+    }
+
     /// Return length of the bitvector (number of bits).
     ///
     /// ```
@@ -152,10 +175,40 @@ impl Bitvector {
         self.n
     }
 
+    /// Returns `true` if the bitvector does not contain any bits.
+    ///
+    /// ```
+    /// use halko_rust::bitvectors::Bitvector;
+    ///
+    /// assert!(Bitvector::build_empty(0).is_empty());
+    /// assert!(!Bitvector::build_empty(5).is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Returns the size of the bitvector in bytes: the data words and the length.
+    ///
+    /// ```
+    /// use halko_rust::bitvectors::Bitvector;
+    ///
+    /// let bv = Bitvector::build_empty(100);
+    /// assert_eq!(bv.size_in_bytes(), 2*8 + 8);
+    /// ```
+    pub fn size_in_bytes(&self) -> usize {
+        8*self.data.len() + 8
+    }
+
+    /// Returns the space breakdown of the bitvector.
+    pub fn structure_tree(&self) -> StructureNode {
+        StructureNode::new("Bitvector", self.size_in_bytes())
+            .with_field("n", self.n as u64)
+    }
+
     /// Returns bit value in the i-th bit.
     pub fn get(&self, i: usize) -> u32 {
         const I: u32 = 1;
-        (&self.data[i/64] >> (i%64)) as u32 & I
+        (self.data[i/64] >> (i%64)) as u32 & I
     }
 
     /// Sets or unsets the i-th bit in the bitvector.
@@ -175,6 +228,72 @@ impl Bitvector {
         }
     }
 
+    /// Returns integer formed by the bits in range `[i,i+len-1]`, where the `i`-th bit is the
+    /// least significant bit of the result. Length `len` must be in range `[0,64]`.
+    ///
+    /// ```
+    /// use halko_rust::bitvectors::Bitvector;
+    ///
+    /// let a: [u32; 7] = [0,1,0,0,1,1,0];
+    /// let bv = Bitvector::build(&a);
+    ///
+    /// assert_eq!(bv.get_int(1, 3), 1);
+    /// assert_eq!(bv.get_int(3, 3), 6);
+    /// assert_eq!(bv.get_int(0, 0), 0);
+    /// ```
+    pub fn get_int(&self, i: usize, len: usize) -> u64 {
+        if len > 64 || i + len > self.n {
+            panic!("[Bitvector::get_int], Invalid range -> i:{}, len:{}, length of bitvector:{}",
+                   i, len, self.n);
+        }
+        if len == 0 {
+            return 0;
+        }
+
+        let k = i/64;
+        let loc_i = i%64;
+        let mut res = self.data[k] >> loc_i;
+        if loc_i + len > 64 {
+            res |= self.data[k+1] << (64-loc_i);
+        }
+        if len == 64 {res} else {res & !(u64::MAX << len)}
+    }
+
+    /// Writes the `len` lowest bits of `val` into range `[i,i+len-1]`, such that the least
+    /// significant bit of `val` goes into the `i`-th bit. Length `len` must be in range `[0,64]`.
+    ///
+    /// ```
+    /// use halko_rust::bitvectors::Bitvector;
+    ///
+    /// let mut bv = Bitvector::build_empty(10);
+    /// bv.set_int(2, 0b101, 3); // [0,0,1,0,1,0,0,0,0,0]
+    ///
+    /// assert_eq!(bv.get(2), 1);
+    /// assert_eq!(bv.get(3), 0);
+    /// assert_eq!(bv.get(4), 1);
+    /// assert_eq!(bv.get_int(2, 3), 5);
+    /// ```
+    pub fn set_int(&mut self, i: usize, val: u64, len: usize) {
+        if len > 64 || i + len > self.n {
+            panic!("[Bitvector::set_int], Invalid range -> i:{}, len:{}, length of bitvector:{}",
+                   i, len, self.n);
+        }
+        if len == 0 {
+            return;
+        }
+
+        let mask = if len == 64 {u64::MAX} else {!(u64::MAX << len)};
+        let val = val & mask;
+        let k = i/64;
+        let loc_i = i%64;
+
+        self.data[k] = (self.data[k] & !(mask << loc_i)) | (val << loc_i);
+        if loc_i + len > 64 {
+            let rest = loc_i + len - 64;
+            self.data[k+1] = (self.data[k+1] & (u64::MAX << rest)) | (val >> (64-loc_i));
+        }
+    }
+
     /// Returns numbers of 1s in the bitvector in range `[0,i]`.
     ///
     /// ```
@@ -201,11 +320,29 @@ impl Bitvector {
 
         let ones_in_block = (self.data[i/64] << (64 - (i%64)-1)).count_ones() as u64;
         // ones in range [0,i-1]
-        let m_i = self.data[0..(i/64)].iter().fold(0, |acc, &x| (acc + x.count_ones() as u64));
+        let m_i = self.count_ones_in_words(0..(i/64));
 
         m_i + ones_in_block
     }
 
+    /// Returns numbers of 1s in the raw data words in range `range`, i.e. in the bits
+    /// `[64*range.start, 64*range.end-1]`. Uses AVX2 or POPCNT on x86-64 and NEON on aarch64,
+    /// when the CPU supports them.
+    ///
+    /// ```
+    /// use halko_rust::bitvectors::Bitvector;
+    ///
+    /// let v: Vec<u64> = vec![u64::MAX, 3, 1];
+    /// let bv = Bitvector::build_from_vec(&v);
+    ///
+    /// assert_eq!(bv.count_ones_in_words(0..3), 67);
+    /// assert_eq!(bv.count_ones_in_words(1..2), 2);
+    /// assert_eq!(bv.count_ones_in_words(1..1), 0);
+    /// ```
+    pub fn count_ones_in_words(&self, range: Range<usize>) -> u64 {
+        popcount::count_ones(&self.data[range])
+    }
+
     /// Returns numbers of 0s in the bitvector in range `[0,i]`.
     ///
     /// ```
@@ -362,67 +499,84 @@ impl Bitvector {
         let j = start/64;
         let k = stop/64;
 
-        // loop blocks
-        for i in j..=k {
-            let mut _next_count = 0;
+        // checking first block if start mod B != 0
+        let mut i = j;
+        if !start.is_multiple_of(64) && j != k {
+            let first_bits = self.data[i] >> (start%64);
+            let next_count = match bit_type {
+                Bit::ZERO => ( ( u64::MAX << (64-(start%64)) ) | first_bits ).count_zeros() as u64,
+                Bit::ONE => first_bits.count_ones() as u64,
+            };
+
+            if next_count >= limit {
+                return self.scan_bits(start, stop, bit_type, limit);
+            }
+            count += next_count;
+            i += 1;
+        }
 
-            // checking first block if start mod B != 0
-            if i == j && start % 64 != 0 && j != k {
-                let first_bits = self.data[i] >> start%64;
-                _next_count = match bit_type {
-                    Bit::ZERO => ( ( u64::MAX << (64-(start%64)) ) | first_bits ).count_zeros() as u64,
-                    Bit::ONE => first_bits.count_ones() as u64,
-                };
+        // full blocks before the last block are counted at once
+        if i < k {
+            let ones = popcount::count_ones(&self.data[i..k]);
+            let next_count = match bit_type {
+                Bit::ZERO => 64*(k-i) as u64 - ones,
+                Bit::ONE => ones,
+            };
 
-                if _next_count >= limit {
-                    return self.scan_bits(start, stop, bit_type, limit);
-                }
-
-            } else if i == k {
-
-                let last_bits = if j != k {
-                    // yyyyXX
-                    // XX0000
-                    self.data[i] << (64-1-(stop%64))
-                } else {
-                    // yyyXXy
-                    // 0yyyXX
-                    // XX0000
-                    (self.data[i] >> (start%64)) << (64-1-((stop%64)-(start%64)))
-                };
-
-                let y = if j != k {i*64} else {start};
-
-                _next_count = match bit_type {
-                    Bit::ZERO => if (stop%64)-(y%64)+1 == 64 {
-                        last_bits.count_zeros() as u64
-                    } else {
-                        ( (u64::MAX >> ((stop%64)-(y%64)+1)) | last_bits ).count_zeros() as u64
-                    },
-                    Bit::ONE => last_bits.count_ones() as u64,
-                };
-
-                if count + _next_count >= limit {
-                    return (limit, self.scan_bits(y, stop, bit_type, limit - count).1);
-                }
+            if count + next_count >= limit {
+                return (limit, self.scan_full_blocks(i, k, bit_type, limit - count));
+            }
+            count += next_count;
+        }
 
+        // last block
+        let last_bits = if j != k {
+            // yyyyXX
+            // XX0000
+            self.data[k] << (64-1-(stop%64))
+        } else {
+            // yyyXXy
+            // 0yyyXX
+            // XX0000
+            (self.data[k] >> (start%64)) << (64-1-((stop%64)-(start%64)))
+        };
+
+        let y = if j != k {k*64} else {start};
+
+        let next_count = match bit_type {
+            Bit::ZERO => if (stop%64)-(y%64)+1 == 64 {
+                last_bits.count_zeros() as u64
             } else {
-                _next_count = match bit_type {
-                    Bit::ZERO => self.data[i].count_zeros() as u64,
-                    Bit::ONE => self.data[i].count_ones() as u64,
-                };
-
-                if count + _next_count >= limit {
-                    return (limit, self.scan_bits(i*64, (i+1)*64, bit_type, limit - count).1);
-                }
-            }
-            count += _next_count;
+                ( (u64::MAX >> ((stop%64)-(y%64)+1)) | last_bits ).count_zeros() as u64
+            },
+            Bit::ONE => last_bits.count_ones() as u64,
+        };
 
+        if count + next_count >= limit {
+            return (limit, self.scan_bits(y, stop, bit_type, limit - count).1);
         }
+        count += next_count;
 
         (count,stop)
     }
 
+    // Index of the `limit`-th counted bit in full blocks `[j,k)`, when the blocks contain at
+    // least `limit` counted bits.
+    fn scan_full_blocks(&self, j: usize, k: usize, bit_type: Bit, limit: u64) -> usize {
+        let mut count = 0;
+        for i in j..k {
+            let next_count = match bit_type {
+                Bit::ZERO => self.data[i].count_zeros() as u64,
+                Bit::ONE => self.data[i].count_ones() as u64,
+            };
+            if count + next_count >= limit {
+                return self.scan_bits(i*64, (i+1)*64 - 1, bit_type, limit - count).1;
+            }
+            count += next_count;
+        }
+        unreachable!()
+    }
+
 }
 
 impl Index<usize> for Bitvector {
@@ -0,0 +1,155 @@
+//! Counting kernels for the number of 1s in a slice of words.
+//!
+//! `count_ones` selects the fastest kernel supported by the CPU at runtime:
+//! AVX2 Harley-Seal or POPCNT on x86-64, NEON `cnt` on aarch64, and portable
+//! `u64::count_ones` loop otherwise.
+
+/// Slices shorter than this are counted with POPCNT instead of AVX2 on x86-64.
+#[cfg(target_arch = "x86_64")]
+const AVX2_MIN_WORDS: usize = 64;
+
+/// Returns the number of 1s in `words` using the fastest available kernel.
+pub(crate) fn count_ones(words: &[u64]) -> u64 {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if words.len() >= AVX2_MIN_WORDS && is_x86_feature_detected!("avx2") {
+            // SAFETY: AVX2 support was checked above
+            return unsafe { count_ones_avx2(words) };
+        }
+        if is_x86_feature_detected!("popcnt") {
+            // SAFETY: POPCNT support was checked above
+            return unsafe { count_ones_popcnt(words) };
+        }
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    {
+        if std::arch::is_aarch64_feature_detected!("neon") {
+            // SAFETY: NEON support was checked above
+            return unsafe { count_ones_neon(words) };
+        }
+    }
+
+    count_ones_scalar(words)
+}
+
+/// Portable kernel, counts the words one by one.
+pub(crate) fn count_ones_scalar(words: &[u64]) -> u64 {
+    words.iter().map(|x| x.count_ones() as u64).sum()
+}
+
+/// Same loop as `count_ones_scalar`, but compiled with POPCNT instruction enabled.
+///
+/// # Safety
+/// CPU must support POPCNT.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "popcnt")]
+pub(crate) unsafe fn count_ones_popcnt(words: &[u64]) -> u64 {
+    words.iter().map(|x| x.count_ones() as u64).sum()
+}
+
+/// Harley-Seal population count with AVX2 (Muła, Kurz, Lemire 2018).
+/// Sixteen 256-bit vectors are combined with carry-save adders, so that the expensive
+/// vector population count is computed only once per sixteen vectors.
+///
+/// # Safety
+/// CPU must support AVX2.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn count_ones_avx2(words: &[u64]) -> u64 {
+    use std::arch::x86_64::*;
+
+    #[target_feature(enable = "avx2")]
+    #[inline]
+    unsafe fn csa(a: __m256i, b: __m256i, c: __m256i) -> (__m256i, __m256i) {
+        let u = _mm256_xor_si256(a, b);
+        let high = _mm256_or_si256(_mm256_and_si256(a, b), _mm256_and_si256(u, c));
+        let low = _mm256_xor_si256(u, c);
+        (high, low)
+    }
+
+    // counts bits of each byte with nibble lookup table, and sums bytes into 64-bit lanes
+    #[target_feature(enable = "avx2")]
+    #[inline]
+    unsafe fn popcount256(v: __m256i) -> __m256i {
+        let lookup = _mm256_setr_epi8(
+            0, 1, 1, 2, 1, 2, 2, 3, 1, 2, 2, 3, 2, 3, 3, 4,
+            0, 1, 1, 2, 1, 2, 2, 3, 1, 2, 2, 3, 2, 3, 3, 4,
+        );
+        let low_mask = _mm256_set1_epi8(0x0f);
+        let lo = _mm256_and_si256(v, low_mask);
+        let hi = _mm256_and_si256(_mm256_srli_epi16(v, 4), low_mask);
+        let cnt = _mm256_add_epi8(_mm256_shuffle_epi8(lookup, lo), _mm256_shuffle_epi8(lookup, hi));
+        _mm256_sad_epu8(cnt, _mm256_setzero_si256())
+    }
+
+    let vectors = words.len() / 4;
+    let ptr = words.as_ptr() as *const __m256i;
+    let load = |i: usize| _mm256_loadu_si256(ptr.add(i));
+
+    let mut total = _mm256_setzero_si256();
+    let mut ones = _mm256_setzero_si256();
+    let mut twos = _mm256_setzero_si256();
+    let mut fours = _mm256_setzero_si256();
+    let mut eights = _mm256_setzero_si256();
+
+    let mut i = 0;
+    while i + 16 <= vectors {
+        let (twos_a, o) = csa(ones, load(i), load(i+1));
+        let (twos_b, o) = csa(o, load(i+2), load(i+3));
+        let (fours_a, t) = csa(twos, twos_a, twos_b);
+        let (twos_a, o) = csa(o, load(i+4), load(i+5));
+        let (twos_b, o) = csa(o, load(i+6), load(i+7));
+        let (fours_b, t) = csa(t, twos_a, twos_b);
+        let (eights_a, f) = csa(fours, fours_a, fours_b);
+        let (twos_a, o) = csa(o, load(i+8), load(i+9));
+        let (twos_b, o) = csa(o, load(i+10), load(i+11));
+        let (fours_a, t) = csa(t, twos_a, twos_b);
+        let (twos_a, o) = csa(o, load(i+12), load(i+13));
+        let (twos_b, o) = csa(o, load(i+14), load(i+15));
+        let (fours_b, t) = csa(t, twos_a, twos_b);
+        let (eights_b, f) = csa(f, fours_a, fours_b);
+        let (sixteens, e) = csa(eights, eights_a, eights_b);
+
+        total = _mm256_add_epi64(total, popcount256(sixteens));
+        ones = o;
+        twos = t;
+        fours = f;
+        eights = e;
+        i += 16;
+    }
+
+    total = _mm256_slli_epi64(total, 4);
+    total = _mm256_add_epi64(total, _mm256_slli_epi64(popcount256(eights), 3));
+    total = _mm256_add_epi64(total, _mm256_slli_epi64(popcount256(fours), 2));
+    total = _mm256_add_epi64(total, _mm256_slli_epi64(popcount256(twos), 1));
+    total = _mm256_add_epi64(total, popcount256(ones));
+
+    while i < vectors {
+        total = _mm256_add_epi64(total, popcount256(load(i)));
+        i += 1;
+    }
+
+    let mut lanes = [0_u64; 4];
+    _mm256_storeu_si256(lanes.as_mut_ptr() as *mut __m256i, total);
+    lanes.iter().sum::<u64>() + count_ones_scalar(&words[vectors*4..])
+}
+
+/// Counts 128 bits at the time with NEON `cnt` instruction.
+///
+/// # Safety
+/// CPU must support NEON.
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon")]
+pub(crate) unsafe fn count_ones_neon(words: &[u64]) -> u64 {
+    use std::arch::aarch64::*;
+
+    let pairs = words.len() / 2;
+    let ptr = words.as_ptr();
+    let mut total: u64 = 0;
+    for i in 0..pairs {
+        let v = vreinterpretq_u8_u64(vld1q_u64(ptr.add(2*i)));
+        total += vaddlvq_u8(vcntq_u8(v)) as u64;
+    }
+    total + count_ones_scalar(&words[pairs*2..])
+}
@@ -1647,3 +1647,138 @@ fn scan_blocks_random_with_limit_start_random() {
         );
     }
 }
+
+// fn: get_int
+#[test]
+fn get_int_matches_get() {
+    let mut rng = thread_rng();
+    let n = 500;
+    let a: Vec<u32> = (0..n).map(|_| rng.gen_range(0..=1)).collect();
+    let bv = Bitvector::build(&a);
+
+    for _ in 0..1000 {
+        let len = rng.gen_range(0..=64);
+        let i = rng.gen_range(0..=n-len);
+        let mut res: u64 = 0;
+        for j in 0..len {
+            res |= (a[i+j] as u64) << j;
+        }
+        assert_eq!(bv.get_int(i, len), res, "i:{}, len:{}", i, len);
+    }
+}
+
+// fn: get_int
+#[test]
+#[should_panic]
+fn get_int_out_of_range() {
+    let bv = Bitvector::build_empty(10);
+    bv.get_int(5, 6);
+}
+
+// fn: set_int, get_int
+#[test]
+fn set_int_random() {
+    let mut rng = thread_rng();
+    let n = 500;
+    let mut a: Vec<u32> = vec![0; n];
+    let mut bv = Bitvector::build_empty(n);
+
+    for _ in 0..1000 {
+        let len = rng.gen_range(0..=64);
+        let i = rng.gen_range(0..=n-len);
+        let val: u64 = rng.gen();
+        bv.set_int(i, val, len);
+        for j in 0..len {
+            a[i+j] = ((val >> j) & 1) as u32;
+        }
+    }
+    for (i, x) in a.iter().enumerate() {
+        assert_eq!(bv.get(i), *x);
+    }
+}
+
+fn random_words(n: usize) -> Vec<u64> {
+    let mut rng = thread_rng();
+    (0..n).map(|_| rng.gen::<u64>() & rng.gen::<u64>()).collect()
+}
+
+// fn: count_ones_in_words
+#[test]
+fn count_ones_in_words_random_ranges() {
+    let mut rng = thread_rng();
+    let v = random_words(3000);
+    let bv = Bitvector::build_from_vec(&v);
+    for _ in 0..500 {
+        let a = rng.gen_range(0..v.len());
+        let b = rng.gen_range(a..=v.len());
+        let res: u64 = v[a..b].iter().map(|x| x.count_ones() as u64).sum();
+        assert_eq!(bv.count_ones_in_words(a..b), res);
+    }
+}
+
+// fn: count_ones_in_words
+#[test]
+fn count_ones_in_words_all_ones() {
+    let v = vec![u64::MAX; 1000];
+    let bv = Bitvector::build_from_vec(&v);
+    for n in 0..=v.len() {
+        assert_eq!(bv.count_ones_in_words(0..n), 64*n as u64);
+    }
+}
+
+// fn: popcount kernels
+#[test]
+fn popcount_kernels_match_scalar() {
+    use crate::bitvectors::popcount;
+
+    let v = random_words(2000);
+    for n in (0..200).chain([511, 512, 513, 1024, 2000]) {
+        let words = &v[..n];
+        let res = popcount::count_ones_scalar(words);
+        assert_eq!(popcount::count_ones(words), res, "n:{}", n);
+
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("popcnt") {
+                assert_eq!(unsafe { popcount::count_ones_popcnt(words) }, res, "popcnt, n:{}", n);
+            }
+            if is_x86_feature_detected!("avx2") {
+                assert_eq!(unsafe { popcount::count_ones_avx2(words) }, res, "avx2, n:{}", n);
+                // unaligned start
+                if n > 1 {
+                    assert_eq!(unsafe { popcount::count_ones_avx2(&words[1..]) },
+                               popcount::count_ones_scalar(&words[1..]), "avx2 unaligned, n:{}", n);
+                }
+            }
+        }
+
+        #[cfg(target_arch = "aarch64")]
+        {
+            if std::arch::is_aarch64_feature_detected!("neon") {
+                assert_eq!(unsafe { popcount::count_ones_neon(words) }, res, "neon, n:{}", n);
+            }
+        }
+    }
+}
+
+// fn: scan_blocks
+#[test]
+fn scan_blocks_long_ranges_with_limit() {
+    let mut rng = thread_rng();
+    let a: Vec<u32> = (0..20_000).map(|_| rng.gen_bool(0.3) as u32).collect();
+    let bv = Bitvector::build(&a);
+    for _ in 0..500 {
+        let x = rng.gen_range(0..a.len());
+        let y = rng.gen_range(0..a.len());
+        let (start, stop) = (x.min(y), x.max(y));
+        for ones in [false, true] {
+            let bit = || if ones {Bit::ONE} else {Bit::ZERO};
+            let total = bv.scan_bits(start, stop, bit(), u64::MAX).0;
+            assert_eq!(bv.scan_blocks(start, stop, bit(), u64::MAX), (total, stop));
+            if total > 0 {
+                let limit = rng.gen_range(1..=total);
+                assert_eq!(bv.scan_blocks(start, stop, bit(), limit), bv.scan_bits(start, stop, bit(), limit));
+            }
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use std::vec::Vec;
+use crate::bitvectors::{Bitvector, Bit};
+use crate::int_vector::{IntVector, bit_length};
+use crate::rank_support::RankSupport;
+use crate::structure::StructureNode;
+
+#[cfg(test)]
+mod tests;
+
+/// Directly Addressable Codes (DAC) for integer sequences with skewed value distribution.
+///
+/// Each value is split into chunks, such that the `k`-th level stores the `k`-th chunk of all
+/// values, which have bits left after the previous levels. Chunks of the level `k` are stored
+/// in `IntVector` using `widths[k]` bits and the level has continuation bitvector with
+/// `RankSupport`, where `1` in index `i` means that the `i`-th value of the level continues
+/// into next level. The index of the value in the next level is `rank1(i)-1`.
+///
+/// Chunk widths are chosen with dynamic programming, such that the total size of chunks and
+/// continuation bits is minimal.
+///
+/// ```
+/// use halko_rust::dac::DacVector;
+///
+/// let v: Vec<u64> = vec![1, 2, 0, 1_000_000, 3, 1];
+/// let dac = DacVector::build(&v);
+///
+/// assert_eq!(dac.len(), 6);
+/// for i in 0..v.len() { assert_eq!(dac.get(i), v[i]); }
+/// ```
+pub struct DacVector {
+    levels: Vec<IntVector>,
+    continuation: Vec<RankSupport>,
+    widths: Vec<usize>,
+    offsets: Vec<usize>, // bit offset of the first chunk bit in each level
+    n: usize,
+}
+
+impl DacVector {
+    /// Builds `DacVector` from the input array using optimal chunk widths.
+    pub fn build(arr: &[u64]) -> DacVector {
+        let widths = DacVector::optimal_widths(arr);
+        DacVector::build_with_widths(arr, &widths)
+    }
+
+    /// Builds `DacVector` from the `IntVector` using optimal chunk widths.
+    ///
+    /// ```
+    /// use halko_rust::int_vector::IntVector;
+    /// use halko_rust::dac::DacVector;
+    ///
+    /// let iv = IntVector::from_slice(&[5, 0, 1_000_000]);
+    ///
+    /// let dac = DacVector::from_int_vector(&iv);
+    /// assert_eq!(dac.get(0), 5);
+    /// assert_eq!(dac.get(1), 0);
+    /// assert_eq!(dac.get(2), 1_000_000);
+    /// ```
+    pub fn from_int_vector(iv: &IntVector) -> DacVector {
+        let v: Vec<u64> = iv.to_vec();
+        DacVector::build(&v)
+    }
+
+    /// Builds `DacVector` from the input array, where the `k`-th level uses `widths[k]` bits.
+    /// Function panics, if the sum of widths is too small to represent the largest value.
+    ///
+    /// ```
+    /// use halko_rust::dac::DacVector;
+    ///
+    /// let v: Vec<u64> = vec![1, 200, 3];
+    /// let dac = DacVector::build_with_widths(&v, &[2, 2, 4]);
+    ///
+    /// assert_eq!(dac.get_widths(), vec![2, 2, 4]);
+    /// for i in 0..v.len() { assert_eq!(dac.get(i), v[i]); }
+    /// ```
+    pub fn build_with_widths(arr: &[u64], widths: &[usize]) -> DacVector {
+        let total: usize = widths.iter().sum();
+        let max_bits = arr.iter().map(|x| bit_length(*x)).max().unwrap_or(0);
+        if widths.is_empty() || widths.contains(&0) || total < max_bits {
+            panic!("[DacVector::build_with_widths], Invalid chunk widths {:?} for values of {} bits.",
+                   widths, max_bits);
+        }
+
+        let mut levels = Vec::new();
+        let mut continuation = Vec::new();
+        let mut level_widths = Vec::new();
+        let mut offsets = Vec::new();
+
+        // values reaching the current level
+        let mut current: Vec<u64> = arr.to_vec();
+        let mut offset = 0;
+
+        for (k, w) in widths.iter().enumerate() {
+            if k > 0 && current.is_empty() {
+                break;
+            }
+            let mask = if *w == 64 {u64::MAX} else {!(u64::MAX << w)};
+            let mut chunks = IntVector::new(current.len(), *w);
+            for (i, x) in current.iter().enumerate() {
+                chunks.set(i, (x >> offset) & mask);
+            }
+            levels.push(chunks);
+            level_widths.push(*w);
+            offsets.push(offset);
+            offset += w;
+
+            let next: Vec<u64> = current.iter().copied().filter(|x| bit_length(*x) > offset).collect();
+            if next.is_empty() {
+                break;
+            }
+
+            let mut bv = Bitvector::build_empty(current.len());
+            for (i, x) in current.iter().enumerate() {
+                if bit_length(*x) > offset {
+                    bv.set(i, Bit::ONE);
+                }
+            }
+            continuation.push(RankSupport::new(bv));
+            current = next;
+        }
+
+        DacVector {
+            levels,
+            continuation,
+            widths: level_widths,
+            offsets,
+            n: arr.len(),
+        }
+    }
+
+    /// Computes chunk widths for the levels, that minimize the number of bits used for chunks
+    /// and continuation bitvectors.
+    ///
+    /// ```
+    /// use halko_rust::dac::DacVector;
+    ///
+    /// // all values fit into single level
+    /// assert_eq!(DacVector::optimal_widths(&[1, 2, 3]), vec![2]);
+    /// ```
+    pub fn optimal_widths(arr: &[u64]) -> Vec<usize> {
+        let max_bits = arr.iter().map(|x| bit_length(*x)).max().unwrap_or(0).max(1);
+
+        // cnt[b] = number of values that need more than b bits, all values are stored at level 0
+        let mut cnt = vec![0_usize; max_bits];
+        for x in arr {
+            for c in cnt.iter_mut().take(bit_length(*x)) {
+                *c += 1;
+            }
+        }
+        cnt[0] = arr.len();
+
+        // cost[s] = minimal cost of storing bits [s, max_bits) of the values
+        let mut cost = vec![0_usize; max_bits+1];
+        let mut next = vec![max_bits; max_bits+1];
+        for s in (0..max_bits).rev() {
+            cost[s] = usize::MAX;
+            for e in s+1..=max_bits {
+                let cont_bits = if e < max_bits {cnt[s]} else {0};
+                let c = cnt[s]*(e-s) + cont_bits + cost[e];
+                if c < cost[s] {
+                    cost[s] = c;
+                    next[s] = e;
+                }
+            }
+        }
+
+        let mut widths = Vec::new();
+        let mut s = 0;
+        while s < max_bits {
+            widths.push(next[s]-s);
+            s = next[s];
+        }
+        widths
+    }
+
+    /// Returns the `i`-th value in O(levels) time.
+    pub fn get(&self, i: usize) -> u64 {
+        if i >= self.n {
+            panic!("[DacVector::get], Index out of bounds, i:{}, DacVector length:{}", i, self.n);
+        }
+
+        let mut res = 0;
+        let mut j = i;
+        for k in 0..self.levels.len() {
+            res |= self.levels[k].get(j) << self.offsets[k];
+            if k == self.continuation.len() || self.continuation[k].get_bv().get(j) == 0 {
+                break;
+            }
+            j = self.continuation[k].rank1(j) as usize - 1;
+        }
+        res
+    }
+
+    /// Returns the length of the DacVector.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    /// Returns `true` if the DacVector does not contain any values.
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Returns the size of the vector in bytes, including the levels and their continuation bits.
+    pub fn size_in_bytes(&self) -> usize {
+        self.levels.iter().map(|iv| iv.size_in_bytes()).sum::<usize>()
+            + self.continuation.iter().map(|rs| rs.size_in_bytes()).sum::<usize>()
+            + 8*self.widths.len() + 8*self.offsets.len() + 8
+    }
+
+    /// Returns the space breakdown of the vector, with the chunks and the continuation bits of each level.
+    ///
+    /// ```
+    /// use halko_rust::dac::DacVector;
+    ///
+    /// let dac = DacVector::build(&[1, 300, 70000]);
+    /// let tree = dac.structure_tree();
+    ///
+    /// assert_eq!(tree.get_size_in_bytes(), dac.size_in_bytes());
+    /// assert_eq!(tree.get_children()[0].get_name(), "level0");
+    /// ```
+    pub fn structure_tree(&self) -> StructureNode {
+        let mut node = StructureNode::new("DacVector", self.size_in_bytes())
+            .with_field("levels", self.levels.len() as u64)
+            .with_field("n", self.n as u64);
+        for (k, iv) in self.levels.iter().enumerate() {
+            node = node.with_child(iv.structure_tree().with_name(&format!("level{}", k)));
+        }
+        for (k, rs) in self.continuation.iter().enumerate() {
+            node = node.with_child(rs.structure_tree().with_name(&format!("continuation{}", k)));
+        }
+        node
+    }
+
+    /// Returns the number of levels in use.
+    pub fn num_levels(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// Returns chunk widths of the levels in use.
+    pub fn get_widths(&self) -> Vec<usize> {
+        self.widths.clone()
+    }
+
+    /// Returns reference to the chunks of the `k`-th level.
+    pub fn get_level(&self, k: usize) -> &IntVector {
+        &self.levels[k]
+    }
+}
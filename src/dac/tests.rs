@@ -0,0 +1,100 @@
+use std::vec::Vec;
+use rand::{Rng, thread_rng};
+
+use crate::dac::DacVector;
+use crate::int_vector::IntVector;
+
+// fn: build
+#[test]
+fn build_empty() {
+    let dac = DacVector::build(&[]);
+    assert!(dac.is_empty());
+    assert_eq!(dac.num_levels(), 1);
+}
+
+// fn: build, get
+#[test]
+fn build_zeros() {
+    let v = vec![0; 100];
+    let dac = DacVector::build(&v);
+    for i in 0..v.len() { assert_eq!(dac.get(i), 0); }
+}
+
+// fn: build, get
+#[test]
+fn build_max_values() {
+    let v = vec![u64::MAX, 0, 1, u64::MAX, 7];
+    let dac = DacVector::build(&v);
+    for (i, x) in v.iter().enumerate() { assert_eq!(dac.get(i), *x); }
+}
+
+// fn: build, get
+#[test]
+fn build_skewed_random() {
+    let mut rng = thread_rng();
+    for _ in 0..10 {
+        let n = rng.gen_range(1..2000);
+        let v: Vec<u64> = (0..n).map(|_| {
+            if rng.gen_range(0..100) == 0 {rng.gen_range(0..u64::MAX)} else {rng.gen_range(0..16)}
+        }).collect();
+        let dac = DacVector::build(&v);
+        assert_eq!(dac.len(), n);
+        for (i, x) in v.iter().enumerate() { assert_eq!(dac.get(i), *x); }
+    }
+}
+
+// fn: build_with_widths, get
+#[test]
+fn build_with_widths_random() {
+    let mut rng = thread_rng();
+    let v: Vec<u64> = (0..1000).map(|_| rng.gen_range(0..1 << 20)).collect();
+    for widths in [vec![20], vec![1; 20], vec![4, 4, 4, 8], vec![3, 30]] {
+        let dac = DacVector::build_with_widths(&v, &widths);
+        for (i, x) in v.iter().enumerate() { assert_eq!(dac.get(i), *x); }
+    }
+}
+
+// fn: build_with_widths
+#[test]
+#[should_panic]
+fn build_with_too_small_widths() {
+    let _dac = DacVector::build_with_widths(&[1, 255, 256], &[4, 4]);
+}
+
+// fn: from_int_vector
+#[test]
+fn from_int_vector_random() {
+    let mut rng = thread_rng();
+    let mut iv = IntVector::new(500, 40);
+    for i in 0..iv.len() { iv.set(i, rng.gen_range(0..1 << 39)); }
+    let dac = DacVector::from_int_vector(&iv);
+    for i in 0..iv.len() { assert_eq!(dac.get(i), iv.get(i)); }
+}
+
+// fn: optimal_widths
+#[test]
+fn optimal_widths_skewed() {
+    // 1000 small values and one 32 bit value, storing everything with 32 bits would take
+    // 32032 bits, while two levels take far less
+    let mut v = vec![3; 1000];
+    v.push(u32::MAX as u64);
+    let widths = DacVector::optimal_widths(&v);
+    assert!(widths.len() > 1);
+    assert_eq!(widths.iter().sum::<usize>(), 32);
+    assert_eq!(widths[0], 2);
+}
+
+// fn: optimal_widths
+#[test]
+fn optimal_widths_uniform() {
+    let v: Vec<u64> = (0..256).collect();
+    assert_eq!(DacVector::optimal_widths(&v), vec![8]);
+}
+
+// fn: get
+#[test]
+#[should_panic]
+fn get_index_out_of_bounds() {
+    let dac = DacVector::build(&[1, 2, 3]);
+    dac.get(3);
+}
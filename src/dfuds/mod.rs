@@ -0,0 +1,328 @@
+use std::vec::Vec;
+use crate::balanced_parens::BalancedParens;
+use crate::bitvectors::{Bitvector, Bit};
+use crate::int_vector::{IntVector, bit_length};
+use crate::structure::StructureNode;
+
+#[cfg(test)]
+mod tests;
+
+/// Ordinal tree in depth-first unary degree sequence (DFUDS), taking `2n` bits and the
+/// balanced parentheses support.
+///
+/// The sequence starts with `(`, followed by `d` open parentheses and a `)` for each node of
+/// degree `d` in preorder. Node `v` is described after the `v`th `)`, and the `i`th child of
+/// `v` is found from the match of the `i`th open parenthesis from the right in its description.
+///
+/// DFUDS does not give the depth of a node from the excess, so `depth` and `level_ancestor` use
+/// the depth-first parentheses of the tree, taking `2n` more bits.
+///
+/// Nodes are identified by their preorder ids, the root is 0. When the tree is built from a
+/// parent array, the children of each node are ordered by their ids in the array, and ids can
+/// be mapped with `get_preorder_id` and `get_node_id`.
+///
+/// ```
+/// use halko_rust::dfuds::Dfuds;
+///
+/// //      0
+/// //    / | \
+/// //   1  4  5
+/// //  / \    |
+/// // 2   3   6
+/// let tree = Dfuds::from_degrees(&[3,2,0,0,0,1,0]);
+///
+/// assert_eq!(tree.child(0, 2), 5);
+/// assert_eq!(tree.parent(3), Some(1));
+/// assert_eq!(tree.subtree_size(1), 3);
+/// assert_eq!(tree.depth(6), 2);
+/// assert_eq!(tree.level_ancestor(6, 1), Some(5));
+/// assert_eq!(tree.lca(3, 6), 0);
+/// assert_eq!(tree.lca(2, 3), 1);
+/// ```
+pub struct Dfuds {
+    bp: BalancedParens, // DFUDS sequence
+    dfs: BalancedParens, // depth-first parentheses
+    node_to_pre: Option<IntVector>, // preorder id of each node of the parent array
+    pre_to_node: Option<IntVector>, // node of the parent array of each preorder id
+    n: usize,
+}
+
+impl Dfuds {
+    /// Builds the tree from a parent array, where the root has parent `None`.
+    /// Function panics if the array is empty or does not form a tree.
+    ///
+    /// ```
+    /// use halko_rust::dfuds::Dfuds;
+    ///
+    /// // 2 is the root with children 0 and 3, and 1 is the child of 3
+    /// let tree = Dfuds::from_parents(&[Some(2), Some(3), None, Some(2)]);
+    ///
+    /// assert_eq!(tree.get_preorder_id(2), 0);
+    /// assert_eq!(tree.get_preorder_id(1), 3);
+    /// assert_eq!(tree.get_node_id(2), 3);
+    /// assert_eq!(tree.subtree_size(tree.get_preorder_id(3)), 2);
+    /// ```
+    pub fn from_parents(parents: &[Option<usize>]) -> Dfuds {
+        let n = parents.len();
+        let mut children: Vec<Vec<usize>> = vec![Vec::new(); n];
+        let mut root = None;
+        for (v, p) in parents.iter().enumerate() {
+            match p {
+                Some(p) if *p >= n => {
+                    panic!("[Dfuds::from_parents], Parent out of bounds, v:{}, parent:{}, Dfuds length:{}", v, p, n);
+                }
+                Some(p) => children[*p].push(v),
+                None if root.is_some() => panic!("[Dfuds::from_parents], Multiple roots, v:{}", v),
+                None => root = Some(v),
+            }
+        }
+        let root = match root {
+            Some(root) => root,
+            None => panic!("[Dfuds::from_parents], Tree does not have a root, Dfuds length:{}", n),
+        };
+
+        let l = bit_length(n as u64 - 1);
+        let mut node_to_pre = IntVector::new(n, l);
+        let mut pre_to_node = IntVector::new(n, l);
+        let mut degrees: Vec<usize> = Vec::with_capacity(n);
+        let mut stack = vec![root];
+        while let Some(v) = stack.pop() {
+            node_to_pre.set(v, degrees.len() as u64);
+            pre_to_node.set(degrees.len(), v as u64);
+            degrees.push(children[v].len());
+            stack.extend(children[v].iter().rev());
+        }
+        if degrees.len() != n {
+            panic!("[Dfuds::from_parents], Parent array contains a cycle, reached nodes:{}, Dfuds length:{}", degrees.len(), n);
+        }
+
+        let (bp, dfs) = encode(&degrees);
+        Dfuds {
+            bp: BalancedParens::new(bp),
+            dfs: BalancedParens::new(dfs),
+            node_to_pre: Some(node_to_pre),
+            pre_to_node: Some(pre_to_node),
+            n,
+        }
+    }
+
+    /// Builds the tree from the degrees of the nodes in preorder.
+    /// Function panics if the degrees do not describe a tree.
+    pub fn from_degrees(degrees: &[usize]) -> Dfuds {
+        let n = degrees.len();
+        if n == 0 {
+            panic!("[Dfuds::from_degrees], Tree does not have a root, Dfuds length:{}", n);
+        }
+        // number of nodes that are announced by their parents but not yet described
+        let mut pending = 1;
+        for (v, d) in degrees.iter().enumerate() {
+            if pending == 0 {
+                panic!("[Dfuds::from_degrees], Node is not reachable from the root, v:{}", v);
+            }
+            pending = pending + d - 1;
+        }
+        if pending != 0 {
+            panic!("[Dfuds::from_degrees], Degrees do not sum to n-1, sum:{}, Dfuds length:{}", pending + n - 1, n);
+        }
+
+        let (bp, dfs) = encode(degrees);
+        Dfuds {
+            bp: BalancedParens::new(bp),
+            dfs: BalancedParens::new(dfs),
+            node_to_pre: None,
+            pre_to_node: None,
+            n,
+        }
+    }
+
+    /// Returns the number of nodes.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Returns the DFUDS sequence, where `(` is 1bit.
+    pub fn get_bv(&self) -> &Bitvector {
+        self.bp.get_bv()
+    }
+
+    /// Returns the balanced parentheses support of the DFUDS sequence.
+    pub fn get_bp(&self) -> &BalancedParens {
+        &self.bp
+    }
+
+    /// Returns the preorder id of node `v` of the parent array. Identity for trees built from
+    /// degrees.
+    pub fn get_preorder_id(&self, v: usize) -> usize {
+        self.check(v, "get_preorder_id");
+        match &self.node_to_pre {
+            Some(iv) => iv.get(v) as usize,
+            None => v,
+        }
+    }
+
+    /// Returns the node of the parent array with preorder id `v`. Identity for trees built from
+    /// degrees.
+    pub fn get_node_id(&self, v: usize) -> usize {
+        self.check(v, "get_node_id");
+        match &self.pre_to_node {
+            Some(iv) => iv.get(v) as usize,
+            None => v,
+        }
+    }
+
+    /// Returns the number of children of node `v`.
+    pub fn degree(&self, v: usize) -> usize {
+        self.check(v, "degree");
+        self.select0(v+1) - self.start(v)
+    }
+
+    /// Returns `true` if node `v` does not have children.
+    pub fn is_leaf(&self, v: usize) -> bool {
+        self.degree(v) == 0
+    }
+
+    /// Returns the `i`th child of node `v`, where the first child is 0.
+    /// Function panics if `i>=degree(v)`.
+    pub fn child(&self, v: usize, i: usize) -> usize {
+        let degree = self.degree(v);
+        if i >= degree {
+            panic!("[Dfuds::child], Child index out of bounds, v:{}, i:{}, degree:{}", v, i, degree);
+        }
+        let close = self.bp.find_close(self.select0(v+1) - 1 - i);
+        self.rank0(close)
+    }
+
+    /// Returns the parent of node `v`, or `None` for the root.
+    pub fn parent(&self, v: usize) -> Option<usize> {
+        self.check(v, "parent");
+        if v == 0 {
+            return None;
+        }
+        Some(self.rank0(self.bp.find_open(self.select0(v))))
+    }
+
+    /// Returns the number of nodes in the subtree of node `v`, including `v`.
+    pub fn subtree_size(&self, v: usize) -> usize {
+        self.check(v, "subtree_size");
+        // the subtree of v is described until the excess drops below the excess before v
+        let x = self.start(v) - 1;
+        let end = self.bp.fwd_search(x, -1).unwrap();
+        (end - x)/2 + 1
+    }
+
+    /// Returns the depth of node `v`, where the root has depth 0.
+    pub fn depth(&self, v: usize) -> usize {
+        self.check(v, "depth");
+        self.dfs.excess(self.dfs_open(v)) as usize - 1
+    }
+
+    /// Returns the ancestor of node `v` that is `d` levels above it, or `None` if `d>depth(v)`.
+    pub fn level_ancestor(&self, v: usize, d: usize) -> Option<usize> {
+        let depth = self.depth(v);
+        if d == 0 {
+            return Some(v);
+        }
+        if d > depth {
+            return None;
+        }
+        if d == depth {
+            return Some(0);
+        }
+        // the ancestor opens after the last position of smaller excess
+        let j = self.dfs.bwd_search(self.dfs_open(v), -(d as i64) - 1).unwrap() + 1;
+        Some(self.dfs.get_rank_support().rank1(j) as usize - 1)
+    }
+
+    /// Returns the lowest common ancestor of nodes `u` and `v`.
+    pub fn lca(&self, u: usize, v: usize) -> usize {
+        self.check(u, "lca");
+        self.check(v, "lca");
+        let (u, v) = (u.min(v), u.max(v));
+        if v < u + self.subtree_size(u) {
+            return u;
+        }
+        // the leftmost minimum excess is before the child of the lca containing v
+        let m = self.bp.rmq(self.start(u), self.select0(v));
+        self.parent(self.rank0(m)).unwrap()
+    }
+
+    /// Returns the size of the structure in bytes.
+    pub fn size_in_bytes(&self) -> usize {
+        self.bp.size_in_bytes() + self.dfs.size_in_bytes()
+            + self.node_to_pre.as_ref().map_or(0, |iv| iv.size_in_bytes())
+            + self.pre_to_node.as_ref().map_or(0, |iv| iv.size_in_bytes()) + 8
+    }
+
+    /// Returns the space breakdown of the structure.
+    pub fn structure_tree(&self) -> StructureNode {
+        let mut node = StructureNode::new("Dfuds", self.size_in_bytes())
+            .with_field("n", self.n as u64)
+            .with_child(self.bp.structure_tree().with_name("bp"))
+            .with_child(self.dfs.structure_tree().with_name("dfs"));
+        if let (Some(a), Some(b)) = (&self.node_to_pre, &self.pre_to_node) {
+            node = node.with_child(a.structure_tree().with_name("node_to_pre"))
+                .with_child(b.structure_tree().with_name("pre_to_node"));
+        }
+        node
+    }
+
+    // Position of the first bit describing the children of node v.
+    fn start(&self, v: usize) -> usize {
+        if v == 0 {1} else {self.select0(v) + 1}
+    }
+
+    // Position of the open parenthesis of node v in the depth-first parentheses.
+    fn dfs_open(&self, v: usize) -> usize {
+        self.dfs.get_rank_support().select1(v+1)
+    }
+
+    fn select0(&self, k: usize) -> usize {
+        self.bp.get_rank_support().select0(k)
+    }
+
+    fn rank0(&self, i: usize) -> usize {
+        self.bp.get_rank_support().rank0(i) as usize
+    }
+
+    fn check(&self, v: usize, f: &str) {
+        if v >= self.n {
+            panic!("[Dfuds::{}], Index out of bounds, v:{}, Dfuds length:{}", f, v, self.n);
+        }
+    }
+}
+
+// DFUDS sequence and depth-first parentheses of the degrees in preorder.
+fn encode(degrees: &[usize]) -> (Bitvector, Bitvector) {
+    let n = degrees.len();
+    let mut bp = Bitvector::build_empty(2*n);
+    bp.set(0, Bit::ONE);
+    let mut pos = 1;
+    for d in degrees {
+        for p in pos..pos+d {
+            bp.set(p, Bit::ONE);
+        }
+        pos += d + 1;
+    }
+
+    // remaining children of the nodes on the path from the root
+    let mut dfs = Bitvector::build_empty(2*n);
+    let mut stack: Vec<usize> = Vec::new();
+    let mut pos = 0;
+    for d in degrees {
+        if let Some(top) = stack.last_mut() {
+            *top -= 1;
+        }
+        dfs.set(pos, Bit::ONE);
+        pos += 1;
+        stack.push(*d);
+        while stack.last() == Some(&0) {
+            stack.pop();
+            pos += 1;
+        }
+    }
+    (bp, dfs)
+}
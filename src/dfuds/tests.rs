@@ -0,0 +1,160 @@
+use std::vec::Vec;
+use rand::{Rng, thread_rng};
+
+use crate::dfuds::Dfuds;
+
+// random parent array, node 0 is not necessarily the root
+fn random_parents(n: usize, max_gap: usize) -> Vec<Option<usize>> {
+    let mut rng = thread_rng();
+    let mut order: Vec<usize> = (0..n).collect();
+    for i in (1..n).rev() {
+        order.swap(i, rng.gen_range(0..=i));
+    }
+    let mut parents = vec![None; n];
+    for i in 1..n {
+        // small gaps make deep trees
+        parents[order[i]] = Some(order[rng.gen_range(i.saturating_sub(max_gap)..i)]);
+    }
+    parents
+}
+
+// fn: from_degrees
+#[test]
+fn dfuds_from_degrees_bits() {
+    let tree = Dfuds::from_degrees(&[3,2,0,0,0,1,0]);
+    let bits: Vec<u32> = (0..tree.get_bv().len()).map(|i| tree.get_bv().get(i)).collect();
+    assert_eq!(bits, vec![1,1,1,1,0,1,1,0,0,0,0,1,0,0]);
+    assert_eq!(tree.len(), 7);
+}
+
+// fn: from_degrees
+#[test]
+fn dfuds_single_node() {
+    let tree = Dfuds::from_degrees(&[0]);
+    assert!(tree.is_leaf(0));
+    assert_eq!(tree.parent(0), None);
+    assert_eq!(tree.subtree_size(0), 1);
+    assert_eq!(tree.depth(0), 0);
+    assert_eq!(tree.level_ancestor(0, 1), None);
+    assert_eq!(tree.lca(0, 0), 0);
+}
+
+// fn: from_degrees
+#[test]
+#[should_panic]
+fn dfuds_from_degrees_unreachable() {
+    // forest of two trees
+    Dfuds::from_degrees(&[1,0,1,0]);
+}
+
+// fn: from_degrees
+#[test]
+#[should_panic]
+fn dfuds_from_degrees_wrong_sum() {
+    Dfuds::from_degrees(&[3,0,0]);
+}
+
+// fn: from_parents
+#[test]
+#[should_panic]
+fn dfuds_from_parents_cycle() {
+    Dfuds::from_parents(&[None, Some(2), Some(1)]);
+}
+
+// fn: child, parent, degree, subtree_size, depth, level_ancestor
+#[test]
+fn dfuds_navigation_random() {
+    let mut rng = thread_rng();
+    for (n, max_gap) in [(1, 1), (2, 1), (10, 10), (1000, 1000), (5000, 3), (50_000, 50_000)] {
+        let parents = random_parents(n, max_gap);
+        let tree = Dfuds::from_parents(&parents);
+        let mut children: Vec<Vec<usize>> = vec![Vec::new(); n];
+        for (v, p) in parents.iter().enumerate() {
+            if let Some(p) = p {
+                children[*p].push(v);
+            }
+        }
+        let pre = |v: usize| tree.get_preorder_id(v);
+
+        // naive depths and subtree sizes in preorder
+        let mut depth = vec![0; n];
+        let mut size = vec![1; n];
+        for v in 1..n {
+            depth[v] = depth[tree.parent(v).unwrap()] + 1;
+        }
+        for v in (1..n).rev() {
+            size[tree.parent(v).unwrap()] += size[v];
+        }
+
+        for v in 0..n {
+            let b = pre(v);
+            assert_eq!(tree.get_node_id(b), v);
+            assert_eq!(tree.parent(b), parents[v].map(pre));
+            assert_eq!(tree.degree(b), children[v].len());
+            for (i, c) in children[v].iter().enumerate() {
+                assert_eq!(tree.child(b, i), pre(*c));
+            }
+            assert_eq!(tree.subtree_size(b), size[b]);
+            assert_eq!(tree.depth(b), depth[b]);
+
+            let d = rng.gen_range(0..=depth[b]+1);
+            let mut a = Some(b);
+            for _ in 0..d {
+                a = a.and_then(|x| tree.parent(x));
+            }
+            assert_eq!(tree.level_ancestor(b, d), a);
+        }
+    }
+}
+
+// fn: lca
+#[test]
+fn dfuds_lca_random() {
+    let mut rng = thread_rng();
+    for (n, max_gap) in [(10, 10), (2000, 2000), (2000, 5), (100_000, 100_000)] {
+        let tree = Dfuds::from_parents(&random_parents(n, max_gap));
+        for _ in 0..2000 {
+            let u = rng.gen_range(0..n);
+            let v = rng.gen_range(0..n);
+            // naive by walking up from the deeper node
+            let (mut x, mut y) = (u, v);
+            while x != y {
+                if tree.depth(x) >= tree.depth(y) {
+                    x = tree.parent(x).unwrap();
+                } else {
+                    y = tree.parent(y).unwrap();
+                }
+            }
+            assert_eq!(tree.lca(u, v), x);
+        }
+    }
+}
+
+// fn: size_in_bytes
+#[test]
+fn dfuds_space() {
+    let parents = random_parents(5000, 5000);
+    let a = Dfuds::from_parents(&parents);
+    let degrees: Vec<usize> = (0..a.len()).map(|v| a.degree(v)).collect();
+    let b = Dfuds::from_degrees(&degrees);
+    assert_eq!(a.get_bv().get_data(), b.get_bv().get_data());
+    assert!(a.size_in_bytes() > b.size_in_bytes());
+    assert_eq!(b.structure_tree().get_children().len(), 2);
+    assert_eq!(b.get_bv().len(), 2*5000);
+}
+
+// fn: child
+#[test]
+#[should_panic]
+fn dfuds_child_out_of_bounds() {
+    let tree = Dfuds::from_degrees(&[2,0,0]);
+    tree.child(1, 0);
+}
+
+// fn: depth
+#[test]
+#[should_panic]
+fn dfuds_depth_out_of_bounds() {
+    let tree = Dfuds::from_degrees(&[2,0,0]);
+    tree.depth(3);
+}
@@ -0,0 +1,323 @@
+use std::vec::Vec;
+use crate::bitvectors::{Bitvector, Bit};
+use crate::int_vector::{IntVector, bit_length};
+use crate::structure::StructureNode;
+
+#[cfg(test)]
+mod tests;
+
+/// Universal codes supported by the `EncVector`.
+/// Each value `x` is encoded as codeword of `x+1`, because the codes are defined only for
+/// positive integers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Coder {
+    EliasGamma,
+    EliasDelta,
+    Fibonacci,
+}
+
+/// Fibonacci numbers `1,2,3,5,8,...` that fit into `u64`.
+const FIBONACCI: [u64; 92] = fibonacci_numbers();
+
+const fn fibonacci_numbers() -> [u64; 92] {
+    let mut fib = [0; 92];
+    fib[0] = 1;
+    fib[1] = 2;
+    let mut i = 2;
+    while i < fib.len() {
+        fib[i] = fib[i-1] + fib[i-2];
+        i += 1;
+    }
+    fib
+}
+
+impl Coder {
+    /// Returns length of the codeword of `x` in bits.
+    ///
+    /// ```
+    /// use halko_rust::enc_vector::Coder;
+    ///
+    /// assert_eq!(Coder::EliasGamma.encoded_len(1), 1);
+    /// assert_eq!(Coder::EliasGamma.encoded_len(4), 5);
+    /// assert_eq!(Coder::EliasDelta.encoded_len(1), 1);
+    /// assert_eq!(Coder::EliasDelta.encoded_len(4), 5);
+    /// assert_eq!(Coder::Fibonacci.encoded_len(1), 2);
+    /// assert_eq!(Coder::Fibonacci.encoded_len(4), 4);
+    /// ```
+    pub fn encoded_len(&self, x: u64) -> usize {
+        if x == 0 {
+            panic!("[Coder::encoded_len], Universal codes are defined only for positive integers.");
+        }
+        match *self {
+            Coder::EliasGamma => 2*(x.ilog2() as usize) + 1,
+            Coder::EliasDelta => {
+                let n = x.ilog2() as usize;
+                Coder::EliasGamma.encoded_len(n as u64 + 1) + n
+            },
+            Coder::Fibonacci => {
+                FIBONACCI.iter().take_while(|f| **f <= x).count() + 1
+            },
+        }
+    }
+
+    /// Writes codeword of `x` into bitvector `bv` starting from bit `i`.
+    /// Returns the index after the last written bit.
+    fn encode(&self, bv: &mut Bitvector, i: usize, x: u64) -> usize {
+        match *self {
+            Coder::EliasGamma => {
+                // N zeros, 1 and N lowest bits of x
+                let n = x.ilog2() as usize;
+                bv.set(i+n, Bit::ONE);
+                bv.set_int(i+n+1, x, n);
+                i + 2*n + 1
+            },
+            Coder::EliasDelta => {
+                let n = x.ilog2() as usize;
+                let j = Coder::EliasGamma.encode(bv, i, n as u64 + 1);
+                bv.set_int(j, x, n);
+                j + n
+            },
+            Coder::Fibonacci => {
+                // Zeckendorf representation from smallest to largest Fibonacci number, ends with 11
+                let k = FIBONACCI.iter().take_while(|f| **f <= x).count();
+                let mut rest = x;
+                for j in (0..k).rev() {
+                    if FIBONACCI[j] <= rest {
+                        bv.set(i+j, Bit::ONE);
+                        rest -= FIBONACCI[j];
+                    }
+                }
+                bv.set(i+k, Bit::ONE);
+                i + k + 1
+            },
+        }
+    }
+
+    /// Decodes codeword starting from bit `i` of bitvector `bv`.
+    /// Returns tuple `(x,j)`, where `x` is decoded value and `j` the index after the codeword.
+    fn decode(&self, bv: &Bitvector, i: usize) -> (u64, usize) {
+        match *self {
+            Coder::EliasGamma => {
+                let window = bv.get_int(i, 64.min(bv.len()-i));
+                let n = window.trailing_zeros() as usize;
+                // N lowest bits of x are written after the 1, the highest bit is implicit
+                let x = if n == 0 {1} else {(1 << n) | bv.get_int(i+n+1, n)};
+                (x, i + 2*n + 1)
+            },
+            Coder::EliasDelta => {
+                let (n, j) = Coder::EliasGamma.decode(bv, i);
+                let n = n as usize - 1;
+                let x = if n == 0 {1} else {(1 << n) | bv.get_int(j, n)};
+                (x, j + n)
+            },
+            Coder::Fibonacci => {
+                let mut x = 0;
+                let mut j = 0;
+                let mut prev = 0;
+                loop {
+                    let b = bv.get(i+j);
+                    if b == 1 && prev == 1 {
+                        return (x, i + j + 1);
+                    }
+                    if b == 1 {
+                        x += FIBONACCI[j];
+                    }
+                    prev = b;
+                    j += 1;
+                }
+            },
+        }
+    }
+}
+
+/// Integer vector, where values are stored as variable length universal codewords in a bitstream.
+/// Random access is supported by storing bit offset of every `k`-th codeword and the prefix sum
+/// of values before it, such that each access decodes at most `k` codewords.
+///
+/// ```
+/// use halko_rust::enc_vector::{EncVector, Coder};
+///
+/// let v: Vec<u64> = vec![3, 0, 12, 1, 1, 100, 7];
+/// let ev = EncVector::build(&v, Coder::EliasDelta, 4);
+///
+/// assert_eq!(ev.len(), 7);
+/// for i in 0..v.len() { assert_eq!(ev.get(i), v[i]); }
+/// assert_eq!(ev.prefix_sum(2), 15);
+/// ```
+pub struct EncVector {
+    bits: Bitvector,
+    sample_offsets: IntVector,
+    sample_sums: IntVector,
+    coder: Coder,
+    k: usize, // sample rate
+    n: usize,
+}
+
+impl EncVector {
+    /// Builds `EncVector` from the input array, where every `k`-th codeword is sampled.
+    /// Function panics if `k=0`, if array contains `u64::MAX` or the sum of values does not fit
+    /// into `u64`.
+    pub fn build(arr: &[u64], coder: Coder, k: usize) -> EncVector {
+        if k == 0 {
+            panic!("[EncVector::build], Sample rate must be greater than 0 (zero).");
+        }
+        if arr.contains(&u64::MAX) {
+            panic!("[EncVector::build], Value u64::MAX can not be encoded.");
+        }
+
+        let total_bits: usize = arr.iter().map(|x| coder.encoded_len(x+1)).sum();
+        let total_sum = arr.iter().try_fold(0_u64, |acc, x| acc.checked_add(*x));
+        let total_sum = match total_sum {
+            Some(s) => s,
+            None => panic!("[EncVector::build], Sum of values does not fit into u64."),
+        };
+
+        let samples_n = arr.len().div_ceil(k);
+        let mut sample_offsets = IntVector::new(samples_n, bit_length(total_bits as u64));
+        let mut sample_sums = IntVector::new(samples_n, bit_length(total_sum));
+
+        let mut bits = Bitvector::build_empty(total_bits);
+        let mut pos = 0;
+        let mut sum = 0;
+        for (i, x) in arr.iter().enumerate() {
+            if i % k == 0 {
+                sample_offsets.set(i/k, pos as u64);
+                sample_sums.set(i/k, sum);
+            }
+            pos = coder.encode(&mut bits, pos, x+1);
+            sum += x;
+        }
+
+        EncVector {
+            bits,
+            sample_offsets,
+            sample_sums,
+            coder,
+            k,
+            n: arr.len(),
+        }
+    }
+
+    /// Builds `EncVector` from the `IntVector`, where every `k`-th codeword is sampled.
+    pub fn from_int_vector(iv: &IntVector, coder: Coder, k: usize) -> EncVector {
+        let v: Vec<u64> = iv.to_vec();
+        EncVector::build(&v, coder, k)
+    }
+
+    /// Returns the `i`-th value by decoding at most `k` codewords.
+    pub fn get(&self, i: usize) -> u64 {
+        if i >= self.n {
+            panic!("[EncVector::get], Index out of bounds, i:{}, EncVector length:{}", i, self.n);
+        }
+        let mut pos = self.sample_offsets.get(i/self.k) as usize;
+        for _ in 0..i%self.k {
+            pos = self.coder.decode(&self.bits, pos).1;
+        }
+        self.coder.decode(&self.bits, pos).0 - 1
+    }
+
+    /// Returns sum of values in range `[0,i]` by decoding at most `k` codewords.
+    ///
+    /// ```
+    /// use halko_rust::enc_vector::{EncVector, Coder};
+    ///
+    /// let ev = EncVector::build(&[1, 2, 3, 4], Coder::Fibonacci, 2);
+    ///
+    /// assert_eq!(ev.prefix_sum(0), 1);
+    /// assert_eq!(ev.prefix_sum(2), 6);
+    /// assert_eq!(ev.prefix_sum(3), 10);
+    /// ```
+    pub fn prefix_sum(&self, i: usize) -> u64 {
+        if i >= self.n {
+            panic!("[EncVector::prefix_sum], Index out of bounds, i:{}, EncVector length:{}", i, self.n);
+        }
+        let mut pos = self.sample_offsets.get(i/self.k) as usize;
+        let mut sum = self.sample_sums.get(i/self.k);
+        for _ in 0..=i%self.k {
+            let (x, next) = self.coder.decode(&self.bits, pos);
+            sum += x - 1;
+            pos = next;
+        }
+        sum
+    }
+
+    /// Returns iterator, that decodes the values sequentially without using the samples.
+    ///
+    /// ```
+    /// use halko_rust::enc_vector::{EncVector, Coder};
+    ///
+    /// let v: Vec<u64> = vec![5, 0, 9, 2];
+    /// let ev = EncVector::build(&v, Coder::EliasGamma, 2);
+    ///
+    /// assert_eq!(ev.iter().collect::<Vec<u64>>(), v);
+    /// ```
+    pub fn iter(&self) -> EncVectorIter<'_> {
+        EncVectorIter {
+            ev: self,
+            pos: 0,
+            i: 0,
+        }
+    }
+
+    /// Returns the length of the EncVector.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    /// Returns `true` if the EncVector does not contain any values.
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Returns the size of the vector in bytes, including the codewords and the samples.
+    pub fn size_in_bytes(&self) -> usize {
+        self.bits.size_in_bytes() + self.sample_offsets.size_in_bytes()
+            + self.sample_sums.size_in_bytes() + 24
+    }
+
+    /// Returns the space breakdown of the vector.
+    pub fn structure_tree(&self) -> StructureNode {
+        StructureNode::new("EncVector", self.size_in_bytes())
+            .with_field("k", self.k as u64)
+            .with_field("n", self.n as u64)
+            .with_child(self.bits.structure_tree().with_name("bits"))
+            .with_child(self.sample_offsets.structure_tree().with_name("sample_offsets"))
+            .with_child(self.sample_sums.structure_tree().with_name("sample_sums"))
+    }
+
+    /// Returns the coder used for the values.
+    pub fn get_coder(&self) -> Coder {
+        self.coder
+    }
+
+    /// Returns the sample rate `k`.
+    pub fn get_sample_rate(&self) -> usize {
+        self.k
+    }
+
+    /// Returns reference to the bitstream of codewords.
+    pub fn get_bits(&self) -> &Bitvector {
+        &self.bits
+    }
+}
+
+/// Sequential iterator over the values of `EncVector`.
+pub struct EncVectorIter<'a> {
+    ev: &'a EncVector,
+    pos: usize, // bit offset of the next codeword
+    i: usize,
+}
+
+impl Iterator for EncVectorIter<'_> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        if self.i >= self.ev.n {
+            return None;
+        }
+        let (x, next) = self.ev.coder.decode(&self.ev.bits, self.pos);
+        self.pos = next;
+        self.i += 1;
+        Some(x - 1)
+    }
+}
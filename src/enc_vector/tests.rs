@@ -0,0 +1,147 @@
+use std::vec::Vec;
+use rand::{Rng, thread_rng};
+
+use crate::enc_vector::{EncVector, Coder};
+use crate::int_vector::IntVector;
+
+const CODERS: [Coder; 3] = [Coder::EliasGamma, Coder::EliasDelta, Coder::Fibonacci];
+
+// fn: encoded_len
+#[test]
+fn encoded_len_small_values() {
+    let gamma = [1, 3, 3, 5, 5, 5, 5, 7];
+    let delta = [1, 4, 4, 5, 5, 5, 5, 8];
+    let fib = [2, 3, 4, 4, 5, 5, 5, 6];
+    for x in 1..=8 {
+        assert_eq!(Coder::EliasGamma.encoded_len(x), gamma[x as usize-1]);
+        assert_eq!(Coder::EliasDelta.encoded_len(x), delta[x as usize-1]);
+        assert_eq!(Coder::Fibonacci.encoded_len(x), fib[x as usize-1]);
+    }
+}
+
+// fn: build
+#[test]
+fn build_bitstream_length() {
+    let v: Vec<u64> = (0..100).collect();
+    for coder in CODERS {
+        let ev = EncVector::build(&v, coder, 8);
+        let total: usize = v.iter().map(|x| coder.encoded_len(x+1)).sum();
+        assert_eq!(ev.get_bits().len(), total);
+    }
+}
+
+// fn: build
+#[test]
+fn build_empty() {
+    for coder in CODERS {
+        let ev = EncVector::build(&[], coder, 4);
+        assert!(ev.is_empty());
+        assert_eq!(ev.iter().count(), 0);
+    }
+}
+
+// fn: build
+#[test]
+#[should_panic]
+fn build_zero_sample_rate() {
+    let _ev = EncVector::build(&[1, 2], Coder::EliasGamma, 0);
+}
+
+// fn: build
+#[test]
+#[should_panic]
+fn build_sum_overflow() {
+    let _ev = EncVector::build(&[u64::MAX-1, 2], Coder::EliasGamma, 1);
+}
+
+// fn: get
+#[test]
+fn get_small_values_all_sample_rates() {
+    let v: Vec<u64> = vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 100, 0, 0, 1];
+    for coder in CODERS {
+        for k in 1..=v.len()+1 {
+            let ev = EncVector::build(&v, coder, k);
+            for (i, x) in v.iter().enumerate() {
+                assert_eq!(ev.get(i), *x, "coder:{:?}, k:{}, i:{}", coder, k, i);
+            }
+        }
+    }
+}
+
+// fn: get
+#[test]
+fn get_large_values() {
+    let v: Vec<u64> = vec![u64::MAX-1, 1 << 63, (1 << 63) - 1, 0];
+    for coder in CODERS {
+        let ev = EncVector::build(&v[0..1], coder, 2);
+        assert_eq!(ev.get(0), v[0]);
+        let ev = EncVector::build(&v[1..], coder, 2);
+        for (i, x) in v[1..].iter().enumerate() {
+            assert_eq!(ev.get(i), *x, "coder:{:?}", coder);
+        }
+    }
+}
+
+// fn: get
+#[test]
+fn get_random() {
+    let mut rng = thread_rng();
+    for coder in CODERS {
+        let n = rng.gen_range(1..2000);
+        let v: Vec<u64> = (0..n).map(|_| {
+            let l = rng.gen_range(1..40);
+            rng.gen_range(0..1 << l)
+        }).collect();
+        let k = rng.gen_range(1..64);
+        let ev = EncVector::build(&v, coder, k);
+        for (i, x) in v.iter().enumerate() {
+            assert_eq!(ev.get(i), *x);
+        }
+    }
+}
+
+// fn: get
+#[test]
+#[should_panic]
+fn get_index_out_of_bounds() {
+    let ev = EncVector::build(&[1, 2, 3], Coder::EliasDelta, 2);
+    ev.get(3);
+}
+
+// fn: prefix_sum
+#[test]
+fn prefix_sum_random() {
+    let mut rng = thread_rng();
+    for coder in CODERS {
+        let v: Vec<u64> = (0..500).map(|_| rng.gen_range(0..1000)).collect();
+        let ev = EncVector::build(&v, coder, 7);
+        let mut sum = 0;
+        for (i, x) in v.iter().enumerate() {
+            sum += x;
+            assert_eq!(ev.prefix_sum(i), sum);
+        }
+    }
+}
+
+// fn: iter
+#[test]
+fn iter_random() {
+    let mut rng = thread_rng();
+    for coder in CODERS {
+        let v: Vec<u64> = (0..1000).map(|_| rng.gen_range(0..1 << 20)).collect();
+        let ev = EncVector::build(&v, coder, 16);
+        assert_eq!(ev.iter().collect::<Vec<u64>>(), v);
+    }
+}
+
+// fn: from_int_vector
+#[test]
+fn from_int_vector_small() {
+    let mut iv = IntVector::new(4, 10);
+    iv.set(0, 1000);
+    iv.set(3, 7);
+    for coder in CODERS {
+        let ev = EncVector::from_int_vector(&iv, coder, 3);
+        assert_eq!(ev.iter().collect::<Vec<u64>>(), vec![1000, 0, 0, 7]);
+    }
+}
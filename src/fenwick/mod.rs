@@ -0,0 +1,199 @@
+use std::vec::Vec;
+use crate::int_vector::IntVector;
+use crate::structure::StructureNode;
+
+#[cfg(test)]
+mod tests;
+
+/// Bit-packed Fenwick tree (binary indexed tree) for dynamic prefix sums.
+///
+/// Node `j` (1-based) of the tree stores the sum of the values in range `(j-2^k, j]`, where `2^k`
+/// is the lowest set bit of `j`. Nodes with the same `k` form the `k`-th level, and because
+/// each of them sums at most `2^k` values of `l` bits, the level is stored into `IntVector` using
+/// `l+k` bits per node.
+///
+/// ```
+/// use halko_rust::fenwick::FenwickTree;
+///
+/// let mut ft = FenwickTree::new(5, 4);
+/// ft.add(0, 3);
+/// ft.add(2, 7);
+/// ft.add(4, 1);
+///
+/// assert_eq!(ft.prefix_sum(1), 3);
+/// assert_eq!(ft.prefix_sum(4), 11);
+///
+/// ft.add(2, -2);
+/// assert_eq!(ft.prefix_sum(4), 9);
+/// assert_eq!(ft.find(4), Some(2));
+/// ```
+pub struct FenwickTree {
+    levels: Vec<IntVector>,
+    l: usize, // size of max value in bits
+    n: usize,
+}
+
+impl FenwickTree {
+    /// Creates Fenwick tree of `n` values containing only 0s, where each value is at most `2^l-1`.
+    /// Function panics if `l+ceil(log2(n))>64`, since the prefix sums would not fit into `u64`.
+    pub fn new(n: usize, l: usize) -> FenwickTree {
+        if l > 64 || l == 0 {
+            panic!("[FenwickTree], Error creating new FenwickTree. Length of values in bits is invalid. Given length:{} ,\
+                   length should be in range [1,64]", l);
+        }
+        // prefix sums of n values take l + ceil(log2 n) bits
+        let log_n = if n <= 1 {0} else {n.next_power_of_two().ilog2() as usize};
+        if l + log_n > 64 {
+            panic!("[FenwickTree::new], Prefix sums do not fit into 64 bits, n:{}, l:{}", n, l);
+        }
+
+        let mut levels = Vec::new();
+        let mut k = 0;
+        while n >> k > 0 {
+            // nodes j <= n, where j = (2m+1)*2^k
+            let count = (n >> k).div_ceil(2);
+            levels.push(IntVector::new(count, l+k));
+            k += 1;
+        }
+
+        FenwickTree {
+            levels,
+            l,
+            n,
+        }
+    }
+
+    /// Builds Fenwick tree from the values of `iv` in O(n) time.
+    /// Values can be later updated, but they must fit into `iv.get_l()` bits.
+    ///
+    /// ```
+    /// use halko_rust::int_vector::IntVector;
+    /// use halko_rust::fenwick::FenwickTree;
+    ///
+    /// let iv = IntVector::from_slice(&[7; 4]);
+    ///
+    /// let ft = FenwickTree::from_int_vector(&iv);
+    /// assert_eq!(ft.prefix_sum(3), 28);
+    /// ```
+    pub fn from_int_vector(iv: &IntVector) -> FenwickTree {
+        let mut ft = FenwickTree::new(iv.len(), iv.get_l());
+        let mut sums: Vec<u64> = iv.to_vec();
+        for j in 1..=ft.n {
+            let parent = j + (j & j.wrapping_neg());
+            if parent <= ft.n {
+                sums[parent-1] += sums[j-1];
+            }
+            ft.set_node(j, sums[j-1]);
+        }
+        ft
+    }
+
+    /// Adds `delta` into the `i`-th value in O(log n) time.
+    /// Function panics if the new value does not fit into `l` bits.
+    pub fn add(&mut self, i: usize, delta: i64) {
+        if i >= self.n {
+            panic!("[FenwickTree::add], Index out of bounds, i:{}, FenwickTree length:{}", i, self.n);
+        }
+
+        let new_val = self.get(i) as i128 + delta as i128;
+        let max_val = if self.l == 64 {u64::MAX as i128} else {(1_i128 << self.l) - 1};
+        if new_val < 0 || new_val > max_val {
+            panic!("[FenwickTree::add], Error updating the value, new value {} is out of range [0,{}].",
+                   new_val, max_val);
+        }
+
+        let mut j = i+1;
+        while j <= self.n {
+            let node = self.get_node(j).wrapping_add_signed(delta);
+            self.set_node(j, node);
+            j += j & j.wrapping_neg();
+        }
+    }
+
+    /// Returns the `i`-th value in O(log n) time.
+    pub fn get(&self, i: usize) -> u64 {
+        let before = if i == 0 {0} else {self.prefix_sum(i-1)};
+        self.prefix_sum(i) - before
+    }
+
+    /// Returns sum of values in range `[0,i]` in O(log n) time.
+    pub fn prefix_sum(&self, i: usize) -> u64 {
+        if i >= self.n {
+            panic!("[FenwickTree::prefix_sum], Index out of bounds, i:{}, FenwickTree length:{}", i, self.n);
+        }
+
+        let mut sum = 0;
+        let mut j = i+1;
+        while j > 0 {
+            sum += self.get_node(j);
+            j -= j & j.wrapping_neg();
+        }
+        sum
+    }
+
+    /// Returns the smallest index `i`, such that the sum of values in range `[0,i]` is at least
+    /// `sum`, or `None` if the sum of all values is less than `sum`. Takes O(log n) time.
+    pub fn find(&self, sum: u64) -> Option<usize> {
+        if self.n == 0 {
+            return None;
+        }
+
+        // largest node j, such that prefix sum of [1,j] is less than sum
+        let mut j = 0;
+        let mut rest = sum;
+        let mut step = 1 << self.n.ilog2();
+        while step > 0 {
+            if j + step <= self.n {
+                let node = self.get_node(j + step);
+                if node < rest {
+                    j += step;
+                    rest -= node;
+                }
+            }
+            step >>= 1;
+        }
+
+        if j < self.n {Some(j)} else {None}
+    }
+
+    /// Returns the length of the FenwickTree.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    /// Returns `true` if the FenwickTree does not contain any values.
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Returns the size of the tree in bytes.
+    pub fn size_in_bytes(&self) -> usize {
+        self.levels.iter().map(|iv| iv.size_in_bytes()).sum::<usize>() + 16
+    }
+
+    /// Returns the space breakdown of the tree, one node per level.
+    pub fn structure_tree(&self) -> StructureNode {
+        let mut node = StructureNode::new("FenwickTree", self.size_in_bytes())
+            .with_field("l", self.l as u64)
+            .with_field("n", self.n as u64);
+        for (k, iv) in self.levels.iter().enumerate() {
+            node = node.with_child(iv.structure_tree().with_name(&format!("level{}", k)));
+        }
+        node
+    }
+
+    /// Returns reference to the nodes of the `k`-th level.
+    pub fn get_level(&self, k: usize) -> &IntVector {
+        &self.levels[k]
+    }
+
+    fn get_node(&self, j: usize) -> u64 {
+        let k = j.trailing_zeros() as usize;
+        self.levels[k].get(j >> (k+1))
+    }
+
+    fn set_node(&mut self, j: usize, val: u64) {
+        let k = j.trailing_zeros() as usize;
+        self.levels[k].set(j >> (k+1), val);
+    }
+}
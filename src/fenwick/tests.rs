@@ -0,0 +1,142 @@
+use std::vec::Vec;
+use rand::{Rng, thread_rng};
+
+use crate::fenwick::FenwickTree;
+use crate::int_vector::IntVector;
+
+// fn: new
+#[test]
+fn new_level_sizes_and_widths() {
+    let ft = FenwickTree::new(10, 5);
+    // nodes: k=0 -> 1,3,5,7,9; k=1 -> 2,6,10; k=2 -> 4; k=3 -> 8
+    let sizes: Vec<usize> = (0..4).map(|k| ft.get_level(k).len()).collect();
+    let widths: Vec<usize> = (0..4).map(|k| ft.get_level(k).get_l()).collect();
+    assert_eq!(sizes, vec![5, 3, 1, 1]);
+    assert_eq!(widths, vec![5, 6, 7, 8]);
+}
+
+// fn: new
+#[test]
+#[should_panic]
+fn new_with_too_large_l_value() {
+    let _ft = FenwickTree::new(10, 65);
+}
+
+// fn: from_int_vector, prefix_sum
+#[test]
+fn from_int_vector_all_sizes() {
+    let mut rng = thread_rng();
+    for n in 1..200 {
+        let mut iv = IntVector::new(n, 7);
+        for i in 0..n { iv.set(i, rng.gen_range(0..128)); }
+        let ft = FenwickTree::from_int_vector(&iv);
+
+        let mut sum = 0;
+        for i in 0..n {
+            sum += iv.get(i);
+            assert_eq!(ft.prefix_sum(i), sum);
+            assert_eq!(ft.get(i), iv.get(i));
+        }
+    }
+}
+
+// fn: add, prefix_sum
+#[test]
+fn add_random() {
+    let mut rng = thread_rng();
+    let n = 500;
+    let mut ft = FenwickTree::new(n, 10);
+    let mut v: Vec<u64> = vec![0; n];
+
+    for _ in 0..2000 {
+        let i = rng.gen_range(0..n);
+        let new_val: u64 = rng.gen_range(0..1024);
+        ft.add(i, new_val as i64 - v[i] as i64);
+        v[i] = new_val;
+
+        let j = rng.gen_range(0..n);
+        assert_eq!(ft.prefix_sum(j), v[0..=j].iter().sum::<u64>());
+    }
+}
+
+// fn: add
+#[test]
+#[should_panic]
+fn add_value_too_large() {
+    let mut ft = FenwickTree::new(4, 3);
+    ft.add(1, 8);
+}
+
+// fn: add
+#[test]
+#[should_panic]
+fn add_negative_value() {
+    let mut ft = FenwickTree::new(4, 3);
+    ft.add(1, 3);
+    ft.add(1, -4);
+}
+
+// fn: add
+#[test]
+fn add_max_values_l_64() {
+    let mut ft = FenwickTree::new(1, 64);
+    ft.add(0, i64::MAX);
+    ft.add(0, i64::MAX);
+    assert_eq!(ft.get(0), u64::MAX - 1);
+    assert_eq!(ft.prefix_sum(0), u64::MAX - 1);
+}
+
+// fn: new, from_int_vector
+#[test]
+fn new_prefix_sum_width_boundary() {
+    // 4 values of 62 bits need 64 bits for the sum
+    let mut iv = IntVector::new(4, 62);
+    for i in 0..4 {
+        iv.set(i, (1 << 62) - 1);
+    }
+    let ft = FenwickTree::from_int_vector(&iv);
+    assert_eq!(ft.prefix_sum(3), u64::MAX - 3);
+    assert_eq!(ft.get_level(2).get_l(), 64);
+}
+
+// fn: new
+#[test]
+#[should_panic]
+fn new_prefix_sum_too_wide() {
+    let _ft = FenwickTree::new(5, 62);
+}
+
+// fn: new
+#[test]
+#[should_panic]
+fn new_l_64_with_two_values() {
+    let _ft = FenwickTree::new(2, 64);
+}
+
+// fn: find
+#[test]
+fn find_random() {
+    let mut rng = thread_rng();
+    for n in 1..100 {
+        let mut ft = FenwickTree::new(n, 4);
+        let mut sums: Vec<u64> = Vec::new();
+        let mut sum = 0;
+        for i in 0..n {
+            let x = rng.gen_range(0..16);
+            ft.add(i, x as i64);
+            sum += x;
+            sums.push(sum);
+        }
+        for x in 0..=sum+1 {
+            assert_eq!(ft.find(x), sums.iter().position(|s| *s >= x), "n:{}, x:{}", n, x);
+        }
+    }
+}
+
+// fn: find
+#[test]
+fn find_empty() {
+    let ft = FenwickTree::new(0, 4);
+    assert!(ft.is_empty());
+    assert_eq!(ft.find(0), None);
+}
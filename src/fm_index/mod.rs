@@ -0,0 +1,254 @@
+use std::vec::Vec;
+use std::ops::Range;
+use crate::bitvectors::{Bitvector, Bit};
+use crate::int_vector::{IntVector, bit_length};
+use crate::rank_support::RankSupport;
+use crate::suffix_array::suffix_array;
+use crate::wavelet_tree::WaveletTree;
+use crate::structure::StructureNode;
+
+#[cfg(test)]
+mod tests;
+
+/// FM-index of a byte text. The index stores the Burrows-Wheeler transform of the text with a
+/// sentinel in a `WaveletTree`, the C array, and every `s`-th value of the suffix array and the
+/// inverse suffix array, where `s` is the sample rate. Symbols are shifted by one in the BWT,
+/// so the sentinel is 0.
+///
+/// Counting takes `O(m log σ)` time for pattern of length `m`, and each located occurrence
+/// or extracted symbol takes at most `s` additional LF steps.
+///
+/// ```
+/// use halko_rust::fm_index::FmIndex;
+///
+/// let fm = FmIndex::new(b"abracadabra", 4);
+///
+/// assert_eq!(fm.count(b"abra"), 2);
+/// assert_eq!(fm.locate(b"abra"), vec![0, 7]);
+/// assert_eq!(fm.extract(4..8), b"cada".to_vec());
+/// ```
+pub struct FmIndex {
+    bwt: WaveletTree,
+    c: IntVector, // c[x] = number of symbols smaller than x in the BWT
+    sampled: RankSupport, // marks the rows with sampled suffix array value
+    sa_samples: IntVector,
+    isa_samples: IntVector, // isa_samples[j] = row of the suffix starting at j*s
+    s: usize, // sample rate
+    n: usize,
+}
+
+impl FmIndex {
+    /// Builds FM-index of `text`, sampling every `sample_rate`-th text position.
+    /// Function panics if `sample_rate=0`.
+    pub fn new(text: &[u8], sample_rate: usize) -> FmIndex {
+        if sample_rate == 0 {
+            panic!("[FmIndex::new], Sample rate must be greater than 0");
+        }
+        let n = text.len();
+        let s = sample_rate;
+        let sa = suffix_array(text);
+        let l = bit_length(n as u64);
+
+        // row 0 is the sentinel suffix, and rows 1..=n are the suffixes of text
+        let sa_at = |i: usize| if i == 0 {n} else {sa.get(i-1) as usize};
+
+        let mut bwt_iv = IntVector::new(n+1, 9);
+        let mut counts = vec![0_u64; 258];
+        let mut marks = Bitvector::build_empty(n+1);
+        let mut sa_samples = IntVector::new(n/s + 1, l);
+        let mut isa_samples = IntVector::new(n/s + 1, l);
+        let mut k = 0;
+        for i in 0..=n {
+            let p = sa_at(i);
+            let x = if p == 0 {0} else {text[p-1] as u64 + 1};
+            bwt_iv.set(i, x);
+            counts[x as usize + 1] += 1;
+            if p % s == 0 {
+                marks.set(i, Bit::ONE);
+                isa_samples.set(p/s, i as u64);
+                sa_samples.set(k, p as u64);
+                k += 1;
+            }
+        }
+
+        let mut c = IntVector::new(258, bit_length(n as u64 + 1));
+        let mut sum = 0;
+        for (x, count) in counts.iter().enumerate() {
+            sum += count;
+            c.set(x, sum);
+        }
+
+        FmIndex {
+            bwt: WaveletTree::new(&bwt_iv),
+            c,
+            sampled: RankSupport::new(marks),
+            sa_samples,
+            isa_samples,
+            s,
+            n,
+        }
+    }
+
+    /// Returns the length of the text.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    pub fn get_sample_rate(&self) -> usize {
+        self.s
+    }
+
+    /// Returns the BWT of the text with the sentinel, symbols shifted by one.
+    pub fn get_bwt(&self) -> &WaveletTree {
+        &self.bwt
+    }
+
+    /// Returns the C array, where the `x`-th value is the number of BWT symbols smaller than `x`.
+    pub fn get_c(&self) -> &IntVector {
+        &self.c
+    }
+
+    /// Returns the number of occurrences of `pattern` in the text.
+    /// The empty pattern occurs at every position of the text.
+    ///
+    /// ```
+    /// use halko_rust::fm_index::FmIndex;
+    ///
+    /// let fm = FmIndex::new(b"mississippi", 2);
+    ///
+    /// assert_eq!(fm.count(b"ssi"), 2);
+    /// assert_eq!(fm.count(b"i"), 4);
+    /// assert_eq!(fm.count(b"sis"), 1);
+    /// assert_eq!(fm.count(b"spa"), 0);
+    /// assert_eq!(fm.count(b""), 11);
+    /// ```
+    pub fn count(&self, pattern: &[u8]) -> usize {
+        match self.backward_search(pattern) {
+            Some((sp, ep)) => ep-sp+1,
+            None => 0,
+        }
+    }
+
+    /// Returns the starting positions of `pattern` in the text in increasing order.
+    ///
+    /// ```
+    /// use halko_rust::fm_index::FmIndex;
+    ///
+    /// let fm = FmIndex::new(b"mississippi", 3);
+    ///
+    /// assert_eq!(fm.locate(b"ssi"), vec![2, 5]);
+    /// assert_eq!(fm.locate(b"i"), vec![1, 4, 7, 10]);
+    /// assert!(fm.locate(b"spa").is_empty());
+    /// ```
+    pub fn locate(&self, pattern: &[u8]) -> Vec<usize> {
+        let mut res: Vec<usize> = match self.backward_search(pattern) {
+            Some((sp, ep)) => (sp..=ep).map(|i| self.locate_row(i)).collect(),
+            None => Vec::new(),
+        };
+        res.sort_unstable();
+        res
+    }
+
+    /// Returns the text in `range`. Function panics if the range is not inside the text.
+    ///
+    /// ```
+    /// use halko_rust::fm_index::FmIndex;
+    ///
+    /// let fm = FmIndex::new(b"mississippi", 3);
+    ///
+    /// assert_eq!(fm.extract(0..4), b"miss".to_vec());
+    /// assert_eq!(fm.extract(8..11), b"ppi".to_vec());
+    /// assert!(fm.extract(5..5).is_empty());
+    /// ```
+    pub fn extract(&self, range: Range<usize>) -> Vec<u8> {
+        if range.start > range.end || range.end > self.n {
+            panic!("[FmIndex::extract], Invalid range, range:{:?}, FmIndex length:{}", range, self.n);
+        }
+        if range.is_empty() {
+            return Vec::new();
+        }
+
+        // first sampled position at or after the end, the suffix at n is in row 0
+        let mut p = range.end.div_ceil(self.s)*self.s;
+        let mut row = if p > self.n {
+            p = self.n;
+            0
+        } else {
+            self.isa_samples.get(p/self.s) as usize
+        };
+
+        let mut res = Vec::with_capacity(range.len());
+        while p > range.start {
+            let x = self.bwt.access(row);
+            p -= 1;
+            if p < range.end {
+                res.push((x-1) as u8);
+            }
+            row = self.lf(row, x);
+        }
+        res.reverse();
+        res
+    }
+
+    /// Returns the size of the index in bytes.
+    pub fn size_in_bytes(&self) -> usize {
+        self.bwt.size_in_bytes() + self.c.size_in_bytes() + self.sampled.size_in_bytes()
+            + self.sa_samples.size_in_bytes() + self.isa_samples.size_in_bytes() + 16
+    }
+
+    /// Returns the space breakdown of the index.
+    pub fn structure_tree(&self) -> StructureNode {
+        StructureNode::new("FmIndex", self.size_in_bytes())
+            .with_field("s", self.s as u64)
+            .with_field("n", self.n as u64)
+            .with_child(self.bwt.structure_tree().with_name("bwt"))
+            .with_child(self.c.structure_tree().with_name("c"))
+            .with_child(self.sampled.structure_tree().with_name("sampled"))
+            .with_child(self.sa_samples.structure_tree().with_name("sa_samples"))
+            .with_child(self.isa_samples.structure_tree().with_name("isa_samples"))
+    }
+
+    // Returns the range [sp,ep] of rows prefixed by the pattern, or None if there are not any.
+    fn backward_search(&self, pattern: &[u8]) -> Option<(usize, usize)> {
+        // current range is [sp,e)
+        let (mut sp, mut e) = (0, self.n+1);
+        for b in pattern.iter().rev() {
+            let x = *b as u64 + 1;
+            sp = (self.c.get(x as usize) + self.occ(x, sp)) as usize;
+            e = (self.c.get(x as usize) + self.occ(x, e)) as usize;
+            if sp >= e {
+                return None;
+            }
+        }
+        // row 0 is the sentinel suffix, which matches only the empty pattern
+        sp = sp.max(1);
+        if sp >= e {None} else {Some((sp, e-1))}
+    }
+
+    // Number of symbols x in rows [0,p) of the BWT.
+    fn occ(&self, x: u64, p: usize) -> u64 {
+        if p == 0 {0} else {self.bwt.rank(x, p-1)}
+    }
+
+    // Row of the suffix starting one position before the suffix of `row`, where x is the BWT
+    // symbol of the row.
+    fn lf(&self, row: usize, x: u64) -> usize {
+        (self.c.get(x as usize) + self.bwt.rank(x, row) - 1) as usize
+    }
+
+    // Text position of the suffix in `row`.
+    fn locate_row(&self, row: usize) -> usize {
+        let mut row = row;
+        let mut steps = 0;
+        while self.sampled.get_bv().get(row) == 0 {
+            row = self.lf(row, self.bwt.access(row));
+            steps += 1;
+        }
+        let k = self.sampled.rank1(row) as usize - 1;
+        self.sa_samples.get(k) as usize + steps
+    }
+}
@@ -0,0 +1,109 @@
+use std::vec::Vec;
+use rand::{Rng, thread_rng};
+
+use crate::fm_index::FmIndex;
+
+fn random_text(n: usize, sigma: u8) -> Vec<u8> {
+    let mut rng = thread_rng();
+    (0..n).map(|_| b'a' + rng.gen_range(0..sigma)).collect()
+}
+
+fn naive_locate(text: &[u8], pattern: &[u8]) -> Vec<usize> {
+    if pattern.len() > text.len() {
+        return Vec::new();
+    }
+    (0..=text.len()-pattern.len()).filter(|i| &text[*i..*i+pattern.len()] == pattern).collect()
+}
+
+// fn: new
+#[test]
+fn fm_new_bwt() {
+    let fm = FmIndex::new(b"banana", 2);
+    // BWT of banana$ is annb$aa
+    let expected = b"annb\x00aa";
+    for (i, x) in expected.iter().enumerate() {
+        let y = if *x == 0 {0} else {*x as u64 + 1};
+        assert_eq!(fm.get_bwt().access(i), y);
+    }
+    assert_eq!(fm.get_c().get(b'a' as usize + 1), 1);
+    assert_eq!(fm.get_c().get(b'b' as usize + 1), 4);
+    assert_eq!(fm.get_c().get(b'n' as usize + 1), 5);
+}
+
+// fn: new
+#[test]
+#[should_panic]
+fn fm_new_zero_sample_rate() {
+    FmIndex::new(b"abc", 0);
+}
+
+// fn: new
+#[test]
+fn fm_empty_text() {
+    let fm = FmIndex::new(b"", 4);
+    assert!(fm.is_empty());
+    assert_eq!(fm.count(b"a"), 0);
+    assert_eq!(fm.count(b""), 0);
+    assert!(fm.locate(b"a").is_empty());
+    assert!(fm.extract(0..0).is_empty());
+}
+
+// fn: count, locate
+#[test]
+fn fm_count_locate_random() {
+    let mut rng = thread_rng();
+    for sigma in [2, 4, 26] {
+        let text = random_text(2000, sigma);
+        for s in [1, 3, 16] {
+            let fm = FmIndex::new(&text, s);
+            for _ in 0..200 {
+                let m = rng.gen_range(1..8);
+                let pattern = if rng.gen_bool(0.5) {
+                    let i = rng.gen_range(0..text.len()-m);
+                    text[i..i+m].to_vec()
+                } else {
+                    random_text(m, sigma)
+                };
+                let expected = naive_locate(&text, &pattern);
+                assert_eq!(fm.count(&pattern), expected.len());
+                assert_eq!(fm.locate(&pattern), expected);
+            }
+        }
+    }
+}
+
+// fn: count
+#[test]
+fn fm_count_all_bytes() {
+    let text: Vec<u8> = (0..=255).chain(0..=255).collect();
+    let fm = FmIndex::new(&text, 5);
+    for b in 0..=255 {
+        assert_eq!(fm.locate(&[b]), vec![b as usize, b as usize + 256]);
+    }
+    assert_eq!(fm.count(&[255, 0]), 1);
+    assert_eq!(fm.count(&[0, 255]), 0);
+}
+
+// fn: extract
+#[test]
+fn fm_extract_random() {
+    let mut rng = thread_rng();
+    let text = random_text(1000, 5);
+    for s in [1, 4, 33, 2000] {
+        let fm = FmIndex::new(&text, s);
+        assert_eq!(fm.extract(0..text.len()), text);
+        for _ in 0..200 {
+            let a = rng.gen_range(0..=text.len());
+            let b = rng.gen_range(a..=text.len());
+            assert_eq!(fm.extract(a..b), text[a..b].to_vec());
+        }
+    }
+}
+
+// fn: extract
+#[test]
+#[should_panic]
+fn fm_extract_out_of_range() {
+    let fm = FmIndex::new(b"abc", 2);
+    fm.extract(1..4);
+}
@@ -0,0 +1,349 @@
+use std::vec::Vec;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap, VecDeque};
+use crate::bitvectors::{Bitvector, Bit};
+use crate::int_vector::IntVector;
+use crate::rank_support::RankSupport;
+use crate::structure::StructureNode;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Child {
+    Node(usize),
+    Leaf(u64),
+}
+
+// Internal node of the tree, `[start,start+len)` is the range of its bits in the concatenation.
+#[derive(Debug, Clone)]
+struct Node {
+    start: usize,
+    len: usize,
+    children: [Child; 2],
+}
+
+/// Wavelet tree shaped by the canonical Huffman code of the symbols. Each symbol is a leaf at
+/// the depth of its code length, so the total number of bits is `n*H0` plus at most `n` bits,
+/// and `access`, `rank` and `select` of a symbol visit one node per bit of its code. On average
+/// a query visits `H0+1` nodes, where `H0` is the zeroth-order entropy of the sequence.
+///
+/// The bitvectors of the internal nodes are concatenated in breadth-first order into one
+/// `Bitvector` with a single `RankSupport`.
+///
+/// ```
+/// use halko_rust::int_vector::IntVector;
+/// use halko_rust::huffman_wavelet_tree::HuffmanWaveletTree;
+///
+/// let a = [7,7,7,7,7,7,1,7,2,7];
+/// let iv = IntVector::from_slice(&a);
+/// let hwt = HuffmanWaveletTree::new(&iv);
+///
+/// assert_eq!(hwt.get_code(7), Some((0, 1)));
+/// assert_eq!(hwt.access(6), 1);
+/// assert_eq!(hwt.rank(7, 9), 8);
+/// assert_eq!(hwt.select(2, 1), 8);
+/// assert_eq!(hwt.get_bits().len(), 12);
+/// ```
+pub struct HuffmanWaveletTree {
+    bits: RankSupport,
+    nodes: Vec<Node>,
+    root: Child,
+    codes: BTreeMap<u64, (u64, usize)>, // symbol -> (code, code length)
+    n: usize,
+}
+
+impl HuffmanWaveletTree {
+    /// Builds Huffman-shaped wavelet tree of the values in `iv`.
+    /// Function panics if some code would be longer than 64 bits.
+    pub fn new(iv: &IntVector) -> HuffmanWaveletTree {
+        let n = iv.len();
+        let values: Vec<u64> = iv.to_vec();
+        let mut freqs: BTreeMap<u64, usize> = BTreeMap::new();
+        for x in values.iter() {
+            *freqs.entry(*x).or_insert(0) += 1;
+        }
+        let codes = canonical_codes(&freqs);
+
+        let total: usize = freqs.iter().map(|(c, f)| f*codes[c].1).sum();
+        let mut bv = Bitvector::build_empty(total);
+        let mut nodes: Vec<Node> = Vec::new();
+        let root = match freqs.keys().next() {
+            Some(c) if freqs.len() == 1 => Child::Leaf(*c),
+            Some(_) => Child::Node(0),
+            None => Child::Leaf(0),
+        };
+
+        // breadth-first construction, the queue holds (node id, depth, values of the node)
+        let mut queue = VecDeque::new();
+        if root == Child::Node(0) {
+            nodes.push(Node {start: 0, len: n, children: [Child::Leaf(0); 2]});
+            queue.push_back((0, 0, values));
+        }
+        let mut pos = 0;
+        while let Some((id, d, vals)) = queue.pop_front() {
+            let mut parts: [Vec<u64>; 2] = [Vec::new(), Vec::new()];
+            for x in vals.iter() {
+                let (code, len) = codes[x];
+                let b = (code >> (len-1-d)) & 1;
+                if b == 1 {
+                    bv.set(pos, Bit::ONE);
+                }
+                pos += 1;
+                parts[b as usize].push(*x);
+            }
+            for (b, part) in parts.into_iter().enumerate() {
+                let c = part[0];
+                nodes[id].children[b] = if codes[&c].1 == d+1 {
+                    Child::Leaf(c)
+                } else {
+                    let child = nodes.len();
+                    nodes.push(Node {start: 0, len: part.len(), children: [Child::Leaf(0); 2]});
+                    queue.push_back((child, d+1, part));
+                    Child::Node(child)
+                };
+            }
+        }
+
+        // node starts in breadth-first order
+        let mut start = 0;
+        for node in nodes.iter_mut() {
+            node.start = start;
+            start += node.len;
+        }
+
+        HuffmanWaveletTree {
+            bits: RankSupport::new(bv),
+            nodes,
+            root,
+            codes,
+            n,
+        }
+    }
+
+    /// Returns the length of the sequence.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Returns the number of internal nodes in the tree.
+    pub fn num_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns the concatenated bitvector of the nodes.
+    pub fn get_bits(&self) -> &Bitvector {
+        self.bits.get_bv()
+    }
+
+    /// Returns the canonical Huffman code of `c` as `(code, length)`, where the first bit of the
+    /// code is the most significant one, or `None` if `c` does not occur in the sequence.
+    /// The only symbol of a sequence with one distinct symbol has code of length 0.
+    pub fn get_code(&self, c: u64) -> Option<(u64, usize)> {
+        self.codes.get(&c).copied()
+    }
+
+    /// Returns the average code length of the sequence, which is the average number of nodes
+    /// visited by `access`.
+    pub fn average_code_length(&self) -> f64 {
+        self.get_bits().len() as f64 / self.n.max(1) as f64
+    }
+
+    /// Returns the `i`-th value of the sequence.
+    ///
+    /// ```
+    /// use halko_rust::int_vector::IntVector;
+    /// use halko_rust::huffman_wavelet_tree::HuffmanWaveletTree;
+    ///
+    /// let iv = IntVector::from_slice(&[9,0,15]);
+    /// let hwt = HuffmanWaveletTree::new(&iv);
+    ///
+    /// assert_eq!(hwt.access(0), 9);
+    /// assert_eq!(hwt.access(1), 0);
+    /// assert_eq!(hwt.access(2), 15);
+    /// ```
+    pub fn access(&self, i: usize) -> u64 {
+        if i >= self.n {
+            panic!("[HuffmanWaveletTree::access], Index out of bounds, i:{}, HuffmanWaveletTree length:{}",
+                   i, self.n);
+        }
+        let mut cur = self.root;
+        let mut p = i;
+        while let Child::Node(id) = cur {
+            let node = &self.nodes[id];
+            let b = self.get_bits().get(node.start + p) as u64;
+            p = self.rank_in_node(node, p, b);
+            cur = node.children[b as usize];
+        }
+        match cur {
+            Child::Leaf(c) => c,
+            Child::Node(_) => unreachable!(),
+        }
+    }
+
+    /// Returns the number of occurrences of `c` in range `[0,i]`.
+    ///
+    /// ```
+    /// use halko_rust::int_vector::IntVector;
+    /// use halko_rust::huffman_wavelet_tree::HuffmanWaveletTree;
+    ///
+    /// let iv = IntVector::from_slice(&[2,0,2,0]);
+    /// let hwt = HuffmanWaveletTree::new(&iv);
+    ///
+    /// assert_eq!(hwt.rank(2, 0), 1);
+    /// assert_eq!(hwt.rank(2, 3), 2);
+    /// assert_eq!(hwt.rank(0, 3), 2);
+    /// assert_eq!(hwt.rank(3, 3), 0);
+    /// ```
+    pub fn rank(&self, c: u64, i: usize) -> u64 {
+        if i >= self.n {
+            panic!("[HuffmanWaveletTree::rank], Index out of bounds, i:{}, HuffmanWaveletTree length:{}",
+                   i, self.n);
+        }
+        let (code, len) = match self.get_code(c) {
+            Some(x) => x,
+            None => return 0,
+        };
+        let mut p = i+1;
+        let mut cur = self.root;
+        for d in 0..len {
+            let node = match cur {
+                Child::Node(id) => &self.nodes[id],
+                Child::Leaf(_) => unreachable!(),
+            };
+            let b = (code >> (len-1-d)) & 1;
+            p = self.rank_in_node(node, p, b);
+            cur = node.children[b as usize];
+        }
+        p as u64
+    }
+
+    /// Returns index of the `k`-th occurrence of `c`.
+    /// Function panics if `k=0` or if `c` occurs less than `k` times.
+    ///
+    /// ```
+    /// use std::panic;
+    /// use halko_rust::int_vector::IntVector;
+    /// use halko_rust::huffman_wavelet_tree::HuffmanWaveletTree;
+    ///
+    /// let iv = IntVector::from_slice(&[2,0,2,0]);
+    /// let hwt = HuffmanWaveletTree::new(&iv);
+    ///
+    /// assert_eq!(hwt.select(2, 2), 2);
+    /// assert_eq!(hwt.select(0, 1), 1);
+    ///
+    /// let panic_result = panic::catch_unwind(|| {
+    ///     hwt.select(2, 3)
+    /// });
+    /// assert!(panic_result.is_err());
+    /// ```
+    pub fn select(&self, c: u64, k: usize) -> usize {
+        let count = if self.n == 0 {0} else {self.rank(c, self.n-1)};
+        if k == 0 || k as u64 > count {
+            panic!("[HuffmanWaveletTree::select], Invalid select query, c:{}, k:{}, number of occurrences:{}",
+                   c, k, count);
+        }
+        let (code, len) = self.codes[&c];
+
+        // internal nodes on the path from the root to the leaf of c
+        let mut path = Vec::with_capacity(len);
+        let mut cur = self.root;
+        for d in 0..len {
+            if let Child::Node(id) = cur {
+                path.push(id);
+                cur = self.nodes[id].children[((code >> (len-1-d)) & 1) as usize];
+            }
+        }
+
+        let mut p = k-1;
+        for (d, id) in path.iter().enumerate().rev() {
+            let start = self.nodes[*id].start;
+            let ones = self.ones_before(start);
+            p = if (code >> (len-1-d)) & 1 == 1 {
+                self.bits.select1(ones + p + 1)
+            } else {
+                self.bits.select0(start - ones + p + 1)
+            } - start;
+        }
+        p
+    }
+
+    /// Returns the size of the tree in bytes, including the nodes and the code table.
+    pub fn size_in_bytes(&self) -> usize {
+        self.bits.size_in_bytes() + 40*self.nodes.len() + 24*self.codes.len() + 24
+    }
+
+    /// Returns the space breakdown of the tree.
+    pub fn structure_tree(&self) -> StructureNode {
+        StructureNode::new("HuffmanWaveletTree", self.size_in_bytes())
+            .with_field("n", self.n as u64)
+            .with_child(self.bits.structure_tree().with_name("bits"))
+            .with_child(StructureNode::new("Vec<Node>", 40*self.nodes.len())
+                        .with_name("nodes")
+                        .with_field("n", self.nodes.len() as u64))
+            .with_child(StructureNode::new("BTreeMap<u64, (u64, usize)>", 24*self.codes.len())
+                        .with_name("codes")
+                        .with_field("n", self.codes.len() as u64))
+    }
+
+    // Number of 1s in range [0,pos) of the concatenated bitvector.
+    fn ones_before(&self, pos: usize) -> usize {
+        if pos == 0 {0} else {self.bits.rank1(pos-1) as usize}
+    }
+
+    // Number of bits `b` among the first `p` bits of the node.
+    fn rank_in_node(&self, node: &Node, p: usize, b: u64) -> usize {
+        let ones = self.ones_before(node.start + p) - self.ones_before(node.start);
+        if b == 1 {ones} else {p - ones}
+    }
+}
+
+// Returns canonical Huffman codes of the symbols with frequencies `freqs`. Codes are assigned
+// in order of (code length, symbol), and a single symbol gets the empty code.
+fn canonical_codes(freqs: &BTreeMap<u64, usize>) -> BTreeMap<u64, (u64, usize)> {
+    let symbols: Vec<u64> = freqs.keys().copied().collect();
+    let m = symbols.len();
+
+    // Huffman tree over m leaves, parent[i] is the parent of the i-th tree node
+    let mut parent = vec![0; 2*m];
+    let mut heap: BinaryHeap<Reverse<(usize, usize)>> = symbols.iter().enumerate()
+        .map(|(i, c)| Reverse((freqs[c], i)))
+        .collect();
+    let mut next = m;
+    while heap.len() > 1 {
+        let Reverse((f1, i1)) = heap.pop().unwrap();
+        let Reverse((f2, i2)) = heap.pop().unwrap();
+        parent[i1] = next;
+        parent[i2] = next;
+        heap.push(Reverse((f1+f2, next)));
+        next += 1;
+    }
+
+    // code lengths are depths of the leaves, root is the last node
+    let mut depth = vec![0; next];
+    for i in (0..next.saturating_sub(1)).rev() {
+        depth[i] = depth[parent[i]] + 1;
+    }
+    let mut order: Vec<(usize, u64)> = (0..m).map(|i| (depth[i], symbols[i])).collect();
+    order.sort();
+    if let Some((len, _)) = order.last() {
+        if *len > 64 {
+            panic!("[HuffmanWaveletTree::new], Huffman code is too long, length:{}", len);
+        }
+    }
+
+    let mut codes = BTreeMap::new();
+    let mut code: u64 = 0;
+    let mut prev_len = order.first().map(|x| x.0).unwrap_or(0);
+    for (len, c) in order.iter() {
+        code = code.checked_shl((len - prev_len) as u32).unwrap_or(0);
+        codes.insert(*c, (code, *len));
+        code = code.wrapping_add(1);
+        prev_len = *len;
+    }
+    codes
+}
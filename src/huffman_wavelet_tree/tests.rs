@@ -0,0 +1,129 @@
+use std::vec::Vec;
+use std::collections::BTreeMap;
+use rand::{Rng, thread_rng};
+
+use crate::huffman_wavelet_tree::HuffmanWaveletTree;
+use crate::int_vector::IntVector;
+
+// geometric distribution over 0..sigma, symbol c has probability about 2^-(c+1)
+fn skewed_values(n: usize, sigma: u64) -> Vec<u64> {
+    let mut rng = thread_rng();
+    (0..n).map(|_| {
+        let mut c = 0;
+        while c+1 < sigma && rng.gen_bool(0.5) {
+            c += 1;
+        }
+        c*1000 + 7
+    }).collect()
+}
+
+fn entropy(values: &[u64]) -> f64 {
+    let mut freqs: BTreeMap<u64, usize> = BTreeMap::new();
+    for x in values.iter() {
+        *freqs.entry(*x).or_insert(0) += 1;
+    }
+    let n = values.len() as f64;
+    freqs.values().map(|f| {
+        let p = *f as f64 / n;
+        -p*p.log2()
+    }).sum()
+}
+
+// fn: new
+#[test]
+fn hwt_new_canonical_codes() {
+    // frequencies a:1, b:1, c:2, d:4
+    let wt = HuffmanWaveletTree::new(&IntVector::from_slice(&[3,3,2,3,1,3,2,0]));
+    assert_eq!(wt.get_code(3), Some((0b0, 1)));
+    assert_eq!(wt.get_code(2), Some((0b10, 2)));
+    assert_eq!(wt.get_code(0), Some((0b110, 3)));
+    assert_eq!(wt.get_code(1), Some((0b111, 3)));
+    assert_eq!(wt.num_nodes(), 3);
+    assert_eq!(wt.get_bits().len(), 4 + 2*2 + 2*3);
+}
+
+// fn: new
+#[test]
+fn hwt_new_space_close_to_entropy() {
+    let a = skewed_values(10_000, 20);
+    let wt = HuffmanWaveletTree::new(&IntVector::from_slice(&a));
+    let h0 = entropy(&a);
+
+    assert!(wt.average_code_length() >= h0);
+    assert!(wt.average_code_length() < h0 + 1.0);
+}
+
+// fn: new
+#[test]
+fn hwt_single_symbol() {
+    let wt = HuffmanWaveletTree::new(&IntVector::from_slice(&[5,5,5,5]));
+    assert_eq!(wt.get_code(5), Some((0, 0)));
+    assert_eq!(wt.num_nodes(), 0);
+    assert_eq!(wt.get_bits().len(), 0);
+
+    for i in 0..4 {
+        assert_eq!(wt.access(i), 5);
+        assert_eq!(wt.rank(5, i), i as u64 + 1);
+        assert_eq!(wt.rank(4, i), 0);
+        assert_eq!(wt.select(5, i+1), i);
+    }
+}
+
+// fn: new
+#[test]
+fn hwt_empty() {
+    let wt = HuffmanWaveletTree::new(&IntVector::new(0, 3));
+    assert!(wt.is_empty());
+    assert_eq!(wt.get_code(0), None);
+}
+
+// fn: access
+#[test]
+fn hwt_access_random() {
+    let mut rng = thread_rng();
+    let uniform: Vec<u64> = (0..3000).map(|_| rng.gen_range(0..300)).collect();
+    for a in [skewed_values(3000, 30), uniform] {
+        let wt = HuffmanWaveletTree::new(&IntVector::from_slice(&a));
+        for (i, x) in a.iter().enumerate() {
+            assert_eq!(wt.access(i), *x);
+        }
+    }
+}
+
+// fn: access
+#[test]
+#[should_panic]
+fn hwt_access_out_of_bounds() {
+    let wt = HuffmanWaveletTree::new(&IntVector::from_slice(&[1,2,3]));
+    wt.access(3);
+}
+
+// fn: rank, select
+#[test]
+fn hwt_rank_select_random() {
+    let a = skewed_values(2000, 12);
+    let wt = HuffmanWaveletTree::new(&IntVector::from_slice(&a));
+    let mut symbols = a.clone();
+    symbols.sort();
+    symbols.dedup();
+    symbols.push(1);
+
+    for c in symbols {
+        let mut count = 0;
+        for (i, x) in a.iter().enumerate() {
+            if *x == c {
+                count += 1;
+                assert_eq!(wt.select(c, count), i);
+            }
+            assert_eq!(wt.rank(c, i), count as u64);
+        }
+    }
+}
+
+// fn: select
+#[test]
+#[should_panic]
+fn hwt_select_missing_symbol() {
+    let wt = HuffmanWaveletTree::new(&IntVector::from_slice(&[1,2,3]));
+    wt.select(0, 1);
+}
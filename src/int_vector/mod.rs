@@ -1,8 +1,11 @@
 use std::fmt;
 use std::vec::Vec;
+use crate::structure::StructureNode;
 
 #[cfg(test)]
 mod tests;
+#[cfg(test)]
+pub(crate) mod test_utils;
 
 /// Compact integer vector structure. Stores each value in the vector using `l` bits.
 ///
@@ -45,7 +48,7 @@ impl IntVector {
             panic!("[IntVector], Error creating new IntVector. Length of values in bits is invalid. Given length:{} ,\
                    length should be in range [1,64]", l);
         }
-        let data: Vec<u64> = vec![0; ((n*l)+64-1)/64];
+        let data: Vec<u64> = vec![0; (n*l).div_ceil(64)];
 
         IntVector {
             l,
@@ -54,6 +57,26 @@ impl IntVector {
         }
     }
 
+    /// Builds `IntVector` of the values, where each value is stored using the number of bits of
+    /// the largest value.
+    ///
+    /// ```
+    /// use halko_rust::int_vector::IntVector;
+    ///
+    /// let iv = IntVector::from_slice(&[3,1,4,1,5]);
+    /// assert_eq!(iv.get_l(), 3);
+    /// assert_eq!(iv.get(4), 5);
+    /// assert_eq!(IntVector::from_slice(&[]).get_l(), 1);
+    /// ```
+    pub fn from_slice(values: &[u64]) -> IntVector {
+        let l = bit_length(values.iter().max().copied().unwrap_or(0));
+        let mut iv = IntVector::new(values.len(), l);
+        for (i, x) in values.iter().enumerate() {
+            iv.set(i, *x);
+        }
+        iv
+    }
+
     /// Set new value `new_val` into the `i`-th value in the `IntVector`
     ///
     /// ```
@@ -100,20 +123,20 @@ impl IntVector {
             let right_neg = !(u64::MAX << loc_i);
             let neg = left_neg | right_neg;
             // clean prev bits
-            self.data[k] = self.data[k] & neg;
+            self.data[k] &= neg;
 
             // set new value
-            self.data[k] = self.data[k] | (new_val << loc_i);
+            self.data[k] |= new_val << loc_i;
         } else {
             // clean data in firts block
-            self.data[k] = self.data[k] & (u64::MAX >> (64-loc_i));
+            self.data[k] &= u64::MAX >> (64-loc_i);
             // set data in first block
-            self.data[k] = self.data[k] | (new_val << loc_i);
+            self.data[k] |= new_val << loc_i;
 
             // clean data in second block
-            self.data[k+1] = self.data[k+1] & (u64::MAX << ((loc_i+self.l)%64));
+            self.data[k+1] &= u64::MAX << ((loc_i+self.l)%64);
             // set data in second block
-            self.data[k+1] = self.data[k+1] | (new_val >> (64-loc_i));
+            self.data[k+1] |= new_val >> (64-loc_i);
         }
 
     }
@@ -162,6 +185,18 @@ impl IntVector {
         (left_part << (64-loc_i)) | right_part
     }
 
+    /// Returns all values of the IntVector in `Vec`.
+    ///
+    /// ```
+    /// use halko_rust::int_vector::IntVector;
+    ///
+    /// let iv = IntVector::from_slice(&[4,0,2]);
+    /// assert_eq!(iv.to_vec(), vec![4,0,2]);
+    /// ```
+    pub fn to_vec(&self) -> Vec<u64> {
+        (0..self.n).map(|i| self.get(i)).collect()
+    }
+
     /// Returns the length of the IntVector.
     ///
     /// ```
@@ -175,6 +210,50 @@ impl IntVector {
         self.n
     }
 
+    /// Returns `true` if the IntVector does not contain any values.
+    ///
+    /// ```
+    /// use halko_rust::int_vector::IntVector;
+    ///
+    /// assert!(IntVector::new(0, 8).is_empty());
+    /// assert!(!IntVector::new(5, 8).is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Returns the length of each value in bits.
+    ///
+    /// ```
+    /// use halko_rust::int_vector::IntVector;
+    ///
+    /// let iv = IntVector::new(5, 8);
+    ///
+    /// assert_eq!(iv.get_l(), 8);
+    /// ```
+    pub fn get_l(&self) -> usize {
+        self.l
+    }
+
+    /// Returns the size of the vector in bytes: the data words, the length and the width.
+    ///
+    /// ```
+    /// use halko_rust::int_vector::IntVector;
+    ///
+    /// let iv = IntVector::new(100, 5);
+    /// assert_eq!(iv.size_in_bytes(), 8*8 + 16);
+    /// ```
+    pub fn size_in_bytes(&self) -> usize {
+        8*self.data.len() + 16
+    }
+
+    /// Returns the space breakdown of the vector.
+    pub fn structure_tree(&self) -> StructureNode {
+        StructureNode::new("IntVector", self.size_in_bytes())
+            .with_field("l", self.l as u64)
+            .with_field("n", self.n as u64)
+    }
+
     /// Returns reference to the raw data of IntVector.
     ///
     /// ```
@@ -202,6 +281,295 @@ impl IntVector {
     pub fn get_data(&self) -> &Vec<u64> {
         &self.data
     }
+
+    /// Creates `IntVector` from raw data, where `data` has `ceil(n*l/64)` words.
+    pub(crate) fn from_raw_parts(data: Vec<u64>, n: usize, l: usize) -> IntVector {
+        IntVector {
+            l,
+            data,
+            n,
+        }
+    }
+
+    /// Increases the length of each value to `l` bits keeping the values.
+    /// Function panics if `l` is smaller than the current length.
+    ///
+    /// ```
+    /// use halko_rust::int_vector::IntVector;
+    ///
+    /// let mut iv = IntVector::new(3, 2);
+    /// iv.set(0, 3);
+    /// iv.expand_width(10);
+    /// iv.set(1, 1000);
+    ///
+    /// assert_eq!(iv.get_l(), 10);
+    /// assert_eq!(iv.get(0), 3);
+    /// assert_eq!(iv.get(1), 1000);
+    /// ```
+    pub fn expand_width(&mut self, l: usize) {
+        if l < self.l || l > 64 {
+            panic!("[IntVector::expand_width], Invalid length of values in bits. Given length:{}, \
+                   length should be in range [{},64]", l, self.l);
+        }
+        if l == self.l {
+            return;
+        }
+
+        let mut res = IntVector::new(self.n, l);
+        for i in 0..self.n {
+            res.set(i, self.get(i));
+        }
+        *self = res;
+    }
+
+    /// Swaps the `i`-th and `j`-th values in the `IntVector`
+    ///
+    /// ```
+    /// use halko_rust::int_vector::IntVector;
+    ///
+    /// let mut iv = IntVector::new(2, 4);
+    /// iv.set(0, 3);
+    /// iv.swap(0, 1);
+    ///
+    /// assert_eq!(iv.get(0), 0);
+    /// assert_eq!(iv.get(1), 3);
+    /// ```
+    pub fn swap(&mut self, i: usize, j: usize) {
+        let tmp = self.get(i);
+        self.set(i, self.get(j));
+        self.set(j, tmp);
+    }
+
+    /// Returns `true` if the values are in non-decreasing order.
+    ///
+    /// ```
+    /// use halko_rust::int_vector::IntVector;
+    ///
+    /// let mut iv = IntVector::new(3, 4);
+    /// assert!(iv.is_sorted());
+    ///
+    /// iv.set(1, 2);
+    /// assert!(!iv.is_sorted());
+    /// ```
+    pub fn is_sorted(&self) -> bool {
+        (1..self.n).all(|i| self.get(i-1) <= self.get(i))
+    }
+
+    /// Sorts the values in non-decreasing order with LSD radix sort, that processes
+    /// `l` bits of the values 8 bits at the time. The sort is stable and uses one
+    /// temporary `IntVector` of the same size.
+    ///
+    /// ```
+    /// use halko_rust::int_vector::IntVector;
+    ///
+    /// let mut iv = IntVector::new(4, 10);
+    /// iv.set(0, 1000);
+    /// iv.set(1, 3);
+    /// iv.set(2, 500);
+    ///
+    /// iv.sort();
+    /// assert_eq!(format!("{iv}"), "[0,3,500,1000,]");
+    /// ```
+    pub fn sort(&mut self) {
+        const RADIX_BITS: usize = 8;
+
+        let mut tmp = IntVector::new(self.n, self.l);
+        let mut shift = 0;
+        while shift < self.l {
+            let mut count = [0_usize; 1 << RADIX_BITS];
+            for i in 0..self.n {
+                count[((self.get(i) >> shift) & 0xff) as usize] += 1;
+            }
+
+            // starting position of each digit
+            let mut sum = 0;
+            for c in count.iter_mut() {
+                let tmp_c = *c;
+                *c = sum;
+                sum += tmp_c;
+            }
+
+            for i in 0..self.n {
+                let x = self.get(i);
+                let d = ((x >> shift) & 0xff) as usize;
+                tmp.set(count[d], x);
+                count[d] += 1;
+            }
+            std::mem::swap(&mut self.data, &mut tmp.data);
+            shift += RADIX_BITS;
+        }
+    }
+
+    /// Sorts the values in non-decreasing order in-place with heapsort.
+    /// The sort is not stable, but it does not allocate.
+    ///
+    /// ```
+    /// use halko_rust::int_vector::IntVector;
+    ///
+    /// let mut iv = IntVector::new(4, 10);
+    /// iv.set(0, 1000);
+    /// iv.set(1, 3);
+    /// iv.set(2, 500);
+    ///
+    /// iv.sort_unstable();
+    /// assert_eq!(format!("{iv}"), "[0,3,500,1000,]");
+    /// ```
+    pub fn sort_unstable(&mut self) {
+        for i in (0..self.n/2).rev() {
+            self.sift_down(i, self.n);
+        }
+        for end in (1..self.n).rev() {
+            self.swap(0, end);
+            self.sift_down(0, end);
+        }
+    }
+
+    fn sift_down(&mut self, i: usize, end: usize) {
+        let mut i = i;
+        loop {
+            let mut largest = i;
+            for child in [2*i+1, 2*i+2] {
+                if child < end && self.get(child) > self.get(largest) {
+                    largest = child;
+                }
+            }
+            if largest == i {
+                return;
+            }
+            self.swap(i, largest);
+            i = largest;
+        }
+    }
+
+    /// Searches value `x` from the sorted `IntVector`. Returns `Ok(i)` if the `i`-th value
+    /// is `x`, and otherwise `Err(i)` where `i` is the index where `x` could be inserted
+    /// keeping the values sorted, same as `slice::binary_search`.
+    ///
+    /// ```
+    /// use halko_rust::int_vector::IntVector;
+    ///
+    /// let mut iv = IntVector::new(3, 4);
+    /// iv.set(0, 1);
+    /// iv.set(1, 5);
+    /// iv.set(2, 9);
+    ///
+    /// assert_eq!(iv.binary_search(5), Ok(1));
+    /// assert_eq!(iv.binary_search(0), Err(0));
+    /// assert_eq!(iv.binary_search(6), Err(2));
+    /// assert_eq!(iv.binary_search(10), Err(3));
+    /// ```
+    pub fn binary_search(&self, x: u64) -> Result<usize, usize> {
+        let i = self.lower_bound(x);
+        if i < self.n && self.get(i) == x {Ok(i)} else {Err(i)}
+    }
+
+    /// Returns index of the first value in the sorted `IntVector`, that is not less than `x`.
+    /// Returns `len()` if all values are less than `x`.
+    ///
+    /// ```
+    /// use halko_rust::int_vector::IntVector;
+    ///
+    /// let mut iv = IntVector::new(4, 4);
+    /// iv.set(1, 3);
+    /// iv.set(2, 3);
+    /// iv.set(3, 7);
+    ///
+    /// assert_eq!(iv.lower_bound(0), 0);
+    /// assert_eq!(iv.lower_bound(3), 1);
+    /// assert_eq!(iv.lower_bound(4), 3);
+    /// assert_eq!(iv.lower_bound(8), 4);
+    /// ```
+    pub fn lower_bound(&self, x: u64) -> usize {
+        self.partition_point(|v| v < x)
+    }
+
+    /// Returns index of the first value in the sorted `IntVector`, that is greater than `x`.
+    /// Returns `len()` if all values are at most `x`.
+    ///
+    /// ```
+    /// use halko_rust::int_vector::IntVector;
+    ///
+    /// let mut iv = IntVector::new(4, 4);
+    /// iv.set(1, 3);
+    /// iv.set(2, 3);
+    /// iv.set(3, 7);
+    ///
+    /// assert_eq!(iv.upper_bound(0), 1);
+    /// assert_eq!(iv.upper_bound(3), 3);
+    /// assert_eq!(iv.upper_bound(7), 4);
+    /// ```
+    pub fn upper_bound(&self, x: u64) -> usize {
+        self.partition_point(|v| v <= x)
+    }
+
+    /// Returns the first index, where predicate `pred` is false, when the predicate is true
+    /// for a prefix of the values and false for the rest.
+    fn partition_point<P: Fn(u64) -> bool>(&self, pred: P) -> usize {
+        let mut low = 0;
+        let mut high = self.n;
+        while low < high {
+            let mid = low + (high-low)/2;
+            if pred(self.get(mid)) {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        low
+    }
+
+    /// Removes consecutive repeated values. If the `IntVector` is sorted, this removes all
+    /// duplicates.
+    ///
+    /// ```
+    /// use halko_rust::int_vector::IntVector;
+    ///
+    /// let mut iv = IntVector::new(5, 4);
+    /// iv.set(2, 3);
+    /// iv.set(3, 3);
+    /// iv.set(4, 1);
+    ///
+    /// iv.dedup();
+    /// assert_eq!(format!("{iv}"), "[0,3,1,]");
+    /// ```
+    pub fn dedup(&mut self) {
+        if self.n == 0 {
+            return;
+        }
+
+        let mut k = 1;
+        for i in 1..self.n {
+            let x = self.get(i);
+            if x != self.get(k-1) {
+                self.set(k, x);
+                k += 1;
+            }
+        }
+        self.truncate(k);
+    }
+
+    /// Shortens the `IntVector` to the first `len` values. Has no effect if `len` is at least
+    /// the current length.
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.n {
+            return;
+        }
+        self.n = len;
+        self.data.truncate((len*self.l).div_ceil(64));
+
+        // clean bits after the last value, so that equal vectors have equal data
+        let rest = (len*self.l) % 64;
+        if rest != 0 {
+            let last = self.data.len()-1;
+            self.data[last] &= !(u64::MAX << rest);
+        }
+    }
+}
+
+/// Returns the number of bits needed to store `x`. Returns 1 for 0, so that the result is
+/// always a valid width of `IntVector`.
+pub(crate) fn bit_length(x: u64) -> usize {
+    if x == 0 {1} else {64 - x.leading_zeros() as usize}
 }
 
 impl fmt::Display for IntVector {
@@ -0,0 +1,13 @@
+use rand::{Rng, thread_rng};
+
+use crate::int_vector::IntVector;
+
+/// Returns `IntVector` of `n` random values of `l` bits.
+pub(crate) fn random_int_vector(n: usize, l: usize) -> IntVector {
+    let mut rng = thread_rng();
+    let mut iv = IntVector::new(n, l);
+    for i in 0..n {
+        iv.set(i, if l == 64 {rng.gen()} else {rng.gen_range(0..1 << l)});
+    }
+    iv
+}
@@ -2,6 +2,7 @@ use std::vec::Vec;
 use rand::{Rng, thread_rng};
 
 use crate::int_vector::IntVector;
+use crate::int_vector::test_utils::random_int_vector;
 
 // fn: new
 #[test]
@@ -238,7 +239,6 @@ fn get_l_64() {
         assert_eq!(iv.get(i), 0);
     }
 
-
     for i in 0..iv.len() {
         iv.set(i, u64::MAX);
         assert_eq!(iv.get(i), u64::MAX);
@@ -389,7 +389,6 @@ fn print_small() {
         iv.set(2, 1);
         iv.set(3, 2);
 
-
         assert_eq!(format!("{iv}"), "[7,0,1,2,0,]");
     }
 }
@@ -437,3 +436,153 @@ fn eq_with_different_l_not_equal() {
     let iv2 = IntVector::new(0,7);
     assert_ne!(iv1, iv2);
 }
+
+// fn: is_sorted
+#[test]
+fn is_sorted_small() {
+    assert!(IntVector::new(0, 3).is_sorted());
+    let mut iv = IntVector::new(4, 3);
+    iv.set(1, 2);
+    iv.set(2, 2);
+    iv.set(3, 7);
+    assert!(iv.is_sorted());
+    iv.set(3, 1);
+    assert!(!iv.is_sorted());
+}
+
+// fn: sort
+#[test]
+fn sort_random_all_widths() {
+    for l in 1..=64 {
+        let mut iv = random_int_vector(300, l);
+        let mut v = iv.to_vec();
+        v.sort();
+        iv.sort();
+        assert!(iv.is_sorted());
+        assert_eq!(iv.to_vec(), v, "l:{}", l);
+    }
+}
+
+// fn: sort_unstable
+#[test]
+fn sort_unstable_random_all_widths() {
+    for l in 1..=64 {
+        let mut iv = random_int_vector(300, l);
+        let mut v = iv.to_vec();
+        v.sort_unstable();
+        iv.sort_unstable();
+        assert_eq!(iv.to_vec(), v, "l:{}", l);
+    }
+}
+
+// fn: sort, sort_unstable
+#[test]
+fn sort_empty_and_single() {
+    let mut iv = IntVector::new(0, 5);
+    iv.sort();
+    iv.sort_unstable();
+    assert_eq!(iv.len(), 0);
+
+    let mut iv = IntVector::new(1, 5);
+    iv.set(0, 17);
+    iv.sort();
+    iv.sort_unstable();
+    assert_eq!(iv.get(0), 17);
+}
+
+// fn: binary_search, lower_bound, upper_bound
+#[test]
+fn search_random() {
+    let mut rng = thread_rng();
+    let mut iv = random_int_vector(500, 10);
+    iv.sort();
+    let v = iv.to_vec();
+    for _ in 0..1000 {
+        let x = rng.gen_range(0..1100);
+        assert_eq!(iv.lower_bound(x), v.partition_point(|y| *y < x));
+        assert_eq!(iv.upper_bound(x), v.partition_point(|y| *y <= x));
+        match iv.binary_search(x) {
+            Ok(i) => assert_eq!(v[i], x),
+            Err(i) => {
+                assert!(!v.contains(&x));
+                assert_eq!(i, v.partition_point(|y| *y < x));
+            },
+        }
+    }
+}
+
+// fn: binary_search
+#[test]
+fn binary_search_empty() {
+    let iv = IntVector::new(0, 5);
+    assert_eq!(iv.binary_search(3), Err(0));
+    assert_eq!(iv.lower_bound(3), 0);
+    assert_eq!(iv.upper_bound(3), 0);
+}
+
+// fn: dedup
+#[test]
+fn dedup_sorted_random() {
+    for l in [1, 3, 7, 33, 64] {
+        let mut iv = random_int_vector(400, l);
+        iv.sort();
+        let mut v = iv.to_vec();
+        v.dedup();
+        iv.dedup();
+        assert_eq!(iv.len(), v.len());
+        assert_eq!(iv.to_vec(), v);
+    }
+}
+
+// fn: dedup, PartialEq
+#[test]
+fn dedup_equal_to_new_vector() {
+    let mut iv = IntVector::from_slice(&[1, 1, 2, 2, 2, 31]);
+    iv.dedup();
+    assert_eq!(iv, IntVector::from_slice(&[1, 2, 31]));
+}
+
+// fn: truncate
+#[test]
+fn truncate_longer_than_length() {
+    let mut iv = random_int_vector(10, 9);
+    let v = iv.to_vec();
+    iv.truncate(20);
+    assert_eq!(iv.to_vec(), v);
+    iv.truncate(4);
+    assert_eq!(iv.to_vec(), v[0..4].to_vec());
+}
+
+// fn: expand_width
+#[test]
+fn expand_width_keeps_values() {
+    for l in 1..=64 {
+        let iv = random_int_vector(50, l);
+        let mut iv2 = iv.clone();
+        iv2.expand_width(64);
+        assert_eq!(iv2.get_l(), 64);
+        assert_eq!(iv2.to_vec(), iv.to_vec());
+    }
+}
+
+// fn: expand_width
+#[test]
+#[should_panic]
+fn expand_width_smaller_width() {
+    let mut iv = IntVector::new(5, 10);
+    iv.expand_width(9);
+}
+
+// fn: from_slice
+#[test]
+fn from_slice_minimal_width() {
+    assert_eq!(IntVector::from_slice(&[]).len(), 0);
+    assert_eq!(IntVector::from_slice(&[0, 0]).get_l(), 1);
+    for l in 1..=64 {
+        let max = if l == 64 {u64::MAX} else {(1 << l) - 1};
+        let values = vec![1, max, max >> 1, 0];
+        let iv = IntVector::from_slice(&values);
+        assert_eq!(iv.get_l(), l);
+        assert_eq!(iv.to_vec(), values);
+    }
+}
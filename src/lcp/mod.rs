@@ -0,0 +1,187 @@
+use std::vec::Vec;
+use crate::bitvectors::{Bitvector, Bit};
+use crate::int_vector::IntVector;
+use crate::rank_support::RankSupport;
+use crate::suffix_array::inverse_suffix_array;
+use crate::structure::StructureNode;
+
+#[cfg(test)]
+mod tests;
+
+/// Builds the LCP array of `text` with Kasai's algorithm in linear time, where `sa` is the
+/// suffix array of `text`. The `i`-th value is the length of the longest common prefix of the
+/// suffixes `sa[i-1]` and `sa[i]`, and the first value is 0. Values are stored with the number
+/// of bits of the largest value.
+///
+/// ```
+/// use halko_rust::suffix_array::suffix_array;
+/// use halko_rust::lcp::lcp_kasai;
+///
+/// let text = b"banana";
+/// let lcp = lcp_kasai(text, &suffix_array(text));
+///
+/// // a, ana, anana, banana, na, nana
+/// let expected = [0,1,3,0,0,2];
+/// for (i, x) in expected.iter().enumerate() {
+///     assert_eq!(lcp.get(i), *x);
+/// }
+/// ```
+pub fn lcp_kasai(text: &[u8], sa: &IntVector) -> IntVector {
+    check_lengths("lcp_kasai", text, sa);
+    let n = text.len();
+    let isa = inverse_suffix_array(sa);
+    let mut lcp = vec![0_u64; n];
+    let mut h = 0;
+    for i in 0..n {
+        let r = isa.get(i) as usize;
+        if r == 0 {
+            h = 0;
+            continue;
+        }
+        let j = sa.get(r-1) as usize;
+        h += common_prefix(text, i+h, j+h);
+        lcp[r] = h as u64;
+        h = h.saturating_sub(1);
+    }
+    IntVector::from_slice(&lcp)
+}
+
+/// Builds the LCP array of `text` with the Φ algorithm, which computes the permuted LCP array
+/// in text order first. The result is the same as with `lcp_kasai`.
+///
+/// ```
+/// use halko_rust::suffix_array::suffix_array;
+/// use halko_rust::lcp::{lcp_kasai, lcp_phi};
+///
+/// let text = b"mississippi";
+/// let sa = suffix_array(text);
+///
+/// assert_eq!(lcp_phi(text, &sa), lcp_kasai(text, &sa));
+/// ```
+pub fn lcp_phi(text: &[u8], sa: &IntVector) -> IntVector {
+    check_lengths("lcp_phi", text, sa);
+    let plcp = plcp_phi(text, sa);
+    let lcp: Vec<u64> = (0..text.len()).map(|i| plcp[sa.get(i) as usize] as u64).collect();
+    IntVector::from_slice(&lcp)
+}
+
+/// Permuted LCP array in 2n bits. The value `PLCP[i]` is the LCP value of the suffix starting
+/// at text position `i`. Since `PLCP[i]+i` is non-decreasing, the values are stored as a
+/// bitvector having 1bit at position `2i+PLCP[i]` for every `i`, and decoded with select.
+///
+/// ```
+/// use halko_rust::suffix_array::suffix_array;
+/// use halko_rust::lcp::{lcp_kasai, PermutedLcp};
+///
+/// let text = b"mississippi";
+/// let sa = suffix_array(text);
+/// let lcp = lcp_kasai(text, &sa);
+/// let plcp = PermutedLcp::new(text, &sa);
+///
+/// for i in 0..text.len() {
+///     assert_eq!(plcp.get(i, &sa), lcp.get(i));
+/// }
+/// assert!(plcp.get_bits().len() <= 2*text.len());
+/// ```
+pub struct PermutedLcp {
+    bits: RankSupport,
+    n: usize,
+}
+
+impl PermutedLcp {
+    /// Builds the permuted LCP array of `text` with the Φ algorithm, where `sa` is the suffix
+    /// array of `text`.
+    pub fn new(text: &[u8], sa: &IntVector) -> PermutedLcp {
+        check_lengths("PermutedLcp::new", text, sa);
+        let n = text.len();
+        let plcp = plcp_phi(text, sa);
+        let mut bv = Bitvector::build_empty(2*n);
+        for (i, x) in plcp.iter().enumerate() {
+            bv.set(2*i + x, Bit::ONE);
+        }
+        PermutedLcp {
+            bits: RankSupport::new(bv),
+            n,
+        }
+    }
+
+    /// Returns the length of the text.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Returns the bitvector of the encoded values.
+    pub fn get_bits(&self) -> &Bitvector {
+        self.bits.get_bv()
+    }
+
+    /// Returns `PLCP[i]`, the LCP value of the suffix starting at text position `i`.
+    pub fn get_plcp(&self, i: usize) -> u64 {
+        if i >= self.n {
+            panic!("[PermutedLcp::get_plcp], Index out of bounds, i:{}, PermutedLcp length:{}", i, self.n);
+        }
+        (self.bits.select1(i+1) - 2*i) as u64
+    }
+
+    /// Returns `LCP[i]` using the suffix array `sa` of the text.
+    pub fn get(&self, i: usize, sa: &IntVector) -> u64 {
+        if i >= self.n {
+            panic!("[PermutedLcp::get], Index out of bounds, i:{}, PermutedLcp length:{}", i, self.n);
+        }
+        self.get_plcp(sa.get(i) as usize)
+    }
+
+    /// Returns the size of the structure in bytes.
+    pub fn size_in_bytes(&self) -> usize {
+        self.bits.size_in_bytes() + 8
+    }
+
+    /// Returns the space breakdown of the structure.
+    pub fn structure_tree(&self) -> StructureNode {
+        StructureNode::new("PermutedLcp", self.size_in_bytes())
+            .with_field("n", self.n as u64)
+            .with_child(self.bits.structure_tree().with_name("bits"))
+    }
+}
+
+// Permuted LCP array in text order with the Φ algorithm.
+fn plcp_phi(text: &[u8], sa: &IntVector) -> Vec<usize> {
+    let n = text.len();
+
+    // phi[sa[i]] = sa[i-1], and the suffix sa[0] has no predecessor
+    let mut phi = vec![usize::MAX; n];
+    for i in 1..n {
+        phi[sa.get(i) as usize] = sa.get(i-1) as usize;
+    }
+
+    let mut plcp = vec![0; n];
+    let mut h = 0;
+    for i in 0..n {
+        if phi[i] == usize::MAX {
+            h = 0;
+            continue;
+        }
+        h += common_prefix(text, i+h, phi[i]+h);
+        plcp[i] = h;
+        h = h.saturating_sub(1);
+    }
+    plcp
+}
+
+// Length of the longest common prefix of the suffixes starting at `a` and `b`.
+fn common_prefix(text: &[u8], a: usize, b: usize) -> usize {
+    text[a.min(text.len())..].iter().zip(text[b.min(text.len())..].iter())
+        .take_while(|(x, y)| x == y)
+        .count()
+}
+
+fn check_lengths(f: &str, text: &[u8], sa: &IntVector) {
+    if text.len() != sa.len() {
+        panic!("[{}], Length of the suffix array does not match, text length:{}, suffix array length:{}",
+               f, text.len(), sa.len());
+    }
+}
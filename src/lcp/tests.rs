@@ -0,0 +1,89 @@
+use std::vec::Vec;
+use rand::{Rng, thread_rng};
+
+use crate::lcp::{lcp_kasai, lcp_phi, PermutedLcp};
+use crate::suffix_array::suffix_array;
+use crate::int_vector::IntVector;
+
+fn naive_lcp(text: &[u8], sa: &IntVector) -> Vec<u64> {
+    (0..sa.len()).map(|i| {
+        if i == 0 {
+            return 0;
+        }
+        let a = &text[sa.get(i-1) as usize..];
+        let b = &text[sa.get(i) as usize..];
+        a.iter().zip(b.iter()).take_while(|(x, y)| x == y).count() as u64
+    }).collect()
+}
+
+fn test_texts() -> Vec<Vec<u8>> {
+    let mut rng = thread_rng();
+    let mut texts: Vec<Vec<u8>> = vec![b"".to_vec(), b"a".to_vec(), b"aaaaaaaaaa".to_vec(),
+                                       b"abababab".to_vec(), b"mississippi".to_vec()];
+    for sigma in [2, 4, 26] {
+        texts.push((0..2000).map(|_| b'a' + rng.gen_range(0..sigma)).collect());
+    }
+    texts
+}
+
+// fn: lcp_kasai
+#[test]
+fn lcp_kasai_naive() {
+    for text in test_texts() {
+        let sa = suffix_array(&text);
+        assert_eq!(lcp_kasai(&text, &sa).to_vec(), naive_lcp(&text, &sa));
+    }
+}
+
+// fn: lcp_kasai
+#[test]
+fn lcp_kasai_minimal_width() {
+    let text = b"aaaaaaaaaa";
+    let lcp = lcp_kasai(text, &suffix_array(text));
+    // largest value is 9
+    assert_eq!(lcp.get_l(), 4);
+}
+
+// fn: lcp_kasai
+#[test]
+#[should_panic]
+fn lcp_kasai_wrong_length() {
+    lcp_kasai(b"abc", &suffix_array(b"ab"));
+}
+
+// fn: lcp_phi
+#[test]
+fn lcp_phi_naive() {
+    for text in test_texts() {
+        let sa = suffix_array(&text);
+        let lcp = lcp_phi(&text, &sa);
+        assert_eq!(lcp.to_vec(), naive_lcp(&text, &sa));
+        assert_eq!(lcp, lcp_kasai(&text, &sa));
+    }
+}
+
+// fn: get, get_plcp
+#[test]
+fn plcp_get_naive() {
+    for text in test_texts() {
+        let sa = suffix_array(&text);
+        let expected = naive_lcp(&text, &sa);
+        let plcp = PermutedLcp::new(&text, &sa);
+
+        assert_eq!(plcp.len(), text.len());
+        assert!(plcp.get_bits().len() <= 2*text.len());
+        for (i, x) in expected.iter().enumerate() {
+            assert_eq!(plcp.get(i, &sa), *x);
+            assert_eq!(plcp.get_plcp(sa.get(i) as usize), *x);
+        }
+    }
+}
+
+// fn: get_plcp
+#[test]
+#[should_panic]
+fn plcp_get_out_of_bounds() {
+    let text = b"abc";
+    let plcp = PermutedLcp::new(text, &suffix_array(text));
+    plcp.get_plcp(3);
+}
@@ -1,3 +1,25 @@
+// tests compare the structures and naive arrays at the same index
+#![cfg_attr(test, allow(clippy::needless_range_loop))]
+
 pub mod bitvectors;
 pub mod rank_support;
 pub mod int_vector;
+pub mod signed_int_vector;
+pub mod dac;
+pub mod enc_vector;
+pub mod vbyte;
+pub mod prefix_sums;
+pub mod fenwick;
+pub mod atomic_vectors;
+pub mod structure;
+pub mod sdsl_io;
+pub mod wavelet_tree;
+pub mod wavelet_matrix;
+pub mod huffman_wavelet_tree;
+pub mod suffix_array;
+pub mod fm_index;
+pub mod lcp;
+pub mod rmq;
+pub mod balanced_parens;
+pub mod louds;
+pub mod dfuds;
@@ -0,0 +1,264 @@
+use std::collections::VecDeque;
+use std::vec::Vec;
+use crate::bitvectors::{Bitvector, Bit};
+use crate::int_vector::{IntVector, bit_length};
+use crate::rank_support::RankSupport;
+use crate::structure::StructureNode;
+
+#[cfg(test)]
+mod tests;
+
+/// Ordinal tree in level-order unary degree sequence (LOUDS), taking `2n+1` bits and the rank
+/// and select support.
+///
+/// The sequence starts with `10` for a virtual super root, followed by `d` 1bits and a 0bit for
+/// each node of degree `d` in breadth-first order. Node `v` is the `(v+1)`th 1bit, and its
+/// children are described after the `(v+1)`th 0bit.
+///
+/// Nodes are identified by their breadth-first ids, the root is 0. When the tree is built from
+/// a parent array, the children of each node are ordered by their ids in the array, and ids can
+/// be mapped with `get_bfs_id` and `get_node_id`.
+///
+/// ```
+/// use halko_rust::louds::Louds;
+///
+/// //      0
+/// //    / | \
+/// //   1  2  3
+/// //  / \    |
+/// // 4   5   6
+/// let tree = Louds::from_degrees(&[3,2,0,1,0,0,0]);
+///
+/// assert_eq!(tree.degree(0), 3);
+/// assert_eq!(tree.child(0, 2), 3);
+/// assert_eq!(tree.first_child(1), Some(4));
+/// assert_eq!(tree.next_sibling(4), Some(5));
+/// assert_eq!(tree.next_sibling(5), None);
+/// assert_eq!(tree.parent(6), Some(3));
+/// assert!(tree.is_leaf(2));
+/// ```
+pub struct Louds {
+    bits: RankSupport,
+    node_to_bfs: Option<IntVector>, // breadth-first id of each node of the parent array
+    bfs_to_node: Option<IntVector>, // node of the parent array of each breadth-first id
+    n: usize,
+}
+
+impl Louds {
+    /// Builds the tree from a parent array, where the root has parent `None`.
+    /// Function panics if the array is empty or does not form a tree.
+    ///
+    /// ```
+    /// use halko_rust::louds::Louds;
+    ///
+    /// // 2 is the root with children 0 and 3, and 1 is the child of 3
+    /// let tree = Louds::from_parents(&[Some(2), Some(3), None, Some(2)]);
+    ///
+    /// assert_eq!(tree.get_bfs_id(2), 0);
+    /// assert_eq!(tree.get_bfs_id(3), 2);
+    /// assert_eq!(tree.get_node_id(3), 1);
+    /// assert_eq!(tree.parent(tree.get_bfs_id(1)), Some(tree.get_bfs_id(3)));
+    /// ```
+    pub fn from_parents(parents: &[Option<usize>]) -> Louds {
+        let n = parents.len();
+        let mut children: Vec<Vec<usize>> = vec![Vec::new(); n];
+        let mut root = None;
+        for (v, p) in parents.iter().enumerate() {
+            match p {
+                Some(p) if *p >= n => {
+                    panic!("[Louds::from_parents], Parent out of bounds, v:{}, parent:{}, Louds length:{}", v, p, n);
+                }
+                Some(p) => children[*p].push(v),
+                None if root.is_some() => panic!("[Louds::from_parents], Multiple roots, v:{}", v),
+                None => root = Some(v),
+            }
+        }
+        let root = match root {
+            Some(root) => root,
+            None => panic!("[Louds::from_parents], Tree does not have a root, Louds length:{}", n),
+        };
+
+        let l = bit_length(n as u64 - 1);
+        let mut node_to_bfs = IntVector::new(n, l);
+        let mut bfs_to_node = IntVector::new(n, l);
+        let mut degrees: Vec<usize> = Vec::with_capacity(n);
+        let mut queue = VecDeque::from([root]);
+        while let Some(v) = queue.pop_front() {
+            node_to_bfs.set(v, degrees.len() as u64);
+            bfs_to_node.set(degrees.len(), v as u64);
+            degrees.push(children[v].len());
+            queue.extend(children[v].iter());
+        }
+        if degrees.len() != n {
+            panic!("[Louds::from_parents], Parent array contains a cycle, reached nodes:{}, Louds length:{}", degrees.len(), n);
+        }
+
+        Louds {
+            bits: RankSupport::new(encode(&degrees)),
+            node_to_bfs: Some(node_to_bfs),
+            bfs_to_node: Some(bfs_to_node),
+            n,
+        }
+    }
+
+    /// Builds the tree from the degrees of the nodes in breadth-first order.
+    /// Function panics if the degrees do not describe a tree.
+    pub fn from_degrees(degrees: &[usize]) -> Louds {
+        let n = degrees.len();
+        if n == 0 {
+            panic!("[Louds::from_degrees], Tree does not have a root, Louds length:{}", n);
+        }
+        // node v has to be a child of an earlier node
+        let mut nodes = 1;
+        for (v, d) in degrees.iter().enumerate() {
+            if v >= nodes {
+                panic!("[Louds::from_degrees], Node is not reachable from the root, v:{}", v);
+            }
+            nodes += d;
+        }
+        if nodes != n {
+            panic!("[Louds::from_degrees], Degrees do not sum to n-1, sum:{}, Louds length:{}", nodes - 1, n);
+        }
+
+        Louds {
+            bits: RankSupport::new(encode(degrees)),
+            node_to_bfs: None,
+            bfs_to_node: None,
+            n,
+        }
+    }
+
+    /// Returns the number of nodes.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Returns the LOUDS sequence.
+    pub fn get_bv(&self) -> &Bitvector {
+        self.bits.get_bv()
+    }
+
+    /// Returns the rank and select support of the LOUDS sequence.
+    pub fn get_rank_support(&self) -> &RankSupport {
+        &self.bits
+    }
+
+    /// Returns the breadth-first id of node `v` of the parent array. Identity for trees built
+    /// from degrees.
+    pub fn get_bfs_id(&self, v: usize) -> usize {
+        self.check(v, "get_bfs_id");
+        match &self.node_to_bfs {
+            Some(iv) => iv.get(v) as usize,
+            None => v,
+        }
+    }
+
+    /// Returns the node of the parent array with breadth-first id `v`. Identity for trees built
+    /// from degrees.
+    pub fn get_node_id(&self, v: usize) -> usize {
+        self.check(v, "get_node_id");
+        match &self.bfs_to_node {
+            Some(iv) => iv.get(v) as usize,
+            None => v,
+        }
+    }
+
+    /// Returns the number of children of node `v`.
+    pub fn degree(&self, v: usize) -> usize {
+        self.check(v, "degree");
+        self.bits.select0(v+2) - self.bits.select0(v+1) - 1
+    }
+
+    /// Returns `true` if node `v` does not have children.
+    pub fn is_leaf(&self, v: usize) -> bool {
+        self.check(v, "is_leaf");
+        self.get_bv().get(self.start(v)) == 0
+    }
+
+    /// Returns the `i`th child of node `v`, where the first child is 0.
+    /// Function panics if `i>=degree(v)`.
+    pub fn child(&self, v: usize, i: usize) -> usize {
+        let degree = self.degree(v);
+        if i >= degree {
+            panic!("[Louds::child], Child index out of bounds, v:{}, i:{}, degree:{}", v, i, degree);
+        }
+        self.bits.rank1(self.start(v) + i) as usize - 1
+    }
+
+    /// Returns the first child of node `v`, or `None` if `v` is a leaf.
+    pub fn first_child(&self, v: usize) -> Option<usize> {
+        if self.is_leaf(v) {
+            None
+        } else {
+            Some(self.bits.rank1(self.start(v)) as usize - 1)
+        }
+    }
+
+    /// Returns the next sibling of node `v`, or `None` if `v` is the last child.
+    pub fn next_sibling(&self, v: usize) -> Option<usize> {
+        self.check(v, "next_sibling");
+        // the 1bit of the root is followed by the 0bit of the super root
+        if self.get_bv().get(self.bits.select1(v+1) + 1) == 1 {
+            Some(v+1)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the parent of node `v`, or `None` for the root.
+    pub fn parent(&self, v: usize) -> Option<usize> {
+        self.check(v, "parent");
+        if v == 0 {
+            return None;
+        }
+        Some(self.bits.rank0(self.bits.select1(v+1)) as usize - 1)
+    }
+
+    /// Returns the size of the structure in bytes.
+    pub fn size_in_bytes(&self) -> usize {
+        self.bits.size_in_bytes()
+            + self.node_to_bfs.as_ref().map_or(0, |iv| iv.size_in_bytes())
+            + self.bfs_to_node.as_ref().map_or(0, |iv| iv.size_in_bytes()) + 8
+    }
+
+    /// Returns the space breakdown of the structure.
+    pub fn structure_tree(&self) -> StructureNode {
+        let mut node = StructureNode::new("Louds", self.size_in_bytes())
+            .with_field("n", self.n as u64)
+            .with_child(self.bits.structure_tree().with_name("bits"));
+        if let (Some(a), Some(b)) = (&self.node_to_bfs, &self.bfs_to_node) {
+            node = node.with_child(a.structure_tree().with_name("node_to_bfs"))
+                .with_child(b.structure_tree().with_name("bfs_to_node"));
+        }
+        node
+    }
+
+    // Position of the first bit describing the children of node v.
+    fn start(&self, v: usize) -> usize {
+        self.bits.select0(v+1) + 1
+    }
+
+    fn check(&self, v: usize, f: &str) {
+        if v >= self.n {
+            panic!("[Louds::{}], Index out of bounds, v:{}, Louds length:{}", f, v, self.n);
+        }
+    }
+}
+
+// LOUDS sequence of the degrees in breadth-first order.
+fn encode(degrees: &[usize]) -> Bitvector {
+    let mut bv = Bitvector::build_empty(2*degrees.len() + 1);
+    bv.set(0, Bit::ONE);
+    let mut pos = 2;
+    for d in degrees {
+        for p in pos..pos+d {
+            bv.set(p, Bit::ONE);
+        }
+        pos += d + 1;
+    }
+    bv
+}
@@ -0,0 +1,124 @@
+use std::vec::Vec;
+use rand::{Rng, thread_rng};
+
+use crate::louds::Louds;
+
+// random parent array, node 0 is not necessarily the root
+fn random_parents(n: usize) -> Vec<Option<usize>> {
+    let mut rng = thread_rng();
+    let mut order: Vec<usize> = (0..n).collect();
+    for i in (1..n).rev() {
+        order.swap(i, rng.gen_range(0..=i));
+    }
+    let mut parents = vec![None; n];
+    for i in 1..n {
+        parents[order[i]] = Some(order[rng.gen_range(0..i)]);
+    }
+    parents
+}
+
+// fn: from_degrees
+#[test]
+fn louds_from_degrees_bits() {
+    let tree = Louds::from_degrees(&[3,2,0,1,0,0,0]);
+    let bits: Vec<u32> = (0..tree.get_bv().len()).map(|i| tree.get_bv().get(i)).collect();
+    assert_eq!(bits, vec![1,0,1,1,1,0,1,1,0,0,1,0,0,0,0]);
+    assert_eq!(tree.len(), 7);
+}
+
+// fn: from_degrees
+#[test]
+fn louds_single_node() {
+    let tree = Louds::from_degrees(&[0]);
+    assert_eq!(tree.degree(0), 0);
+    assert!(tree.is_leaf(0));
+    assert_eq!(tree.first_child(0), None);
+    assert_eq!(tree.next_sibling(0), None);
+    assert_eq!(tree.parent(0), None);
+}
+
+// fn: from_degrees
+#[test]
+#[should_panic]
+fn louds_from_degrees_wrong_sum() {
+    Louds::from_degrees(&[2,0]);
+}
+
+// fn: from_degrees
+#[test]
+#[should_panic]
+fn louds_from_degrees_unreachable() {
+    // forest of two trees
+    Louds::from_degrees(&[1,0,1,0]);
+}
+
+// fn: from_parents
+#[test]
+#[should_panic]
+fn louds_from_parents_cycle() {
+    Louds::from_parents(&[None, Some(2), Some(1)]);
+}
+
+// fn: from_parents
+#[test]
+#[should_panic]
+fn louds_from_parents_two_roots() {
+    Louds::from_parents(&[None, Some(0), None]);
+}
+
+// fn: parent, child, first_child, next_sibling, degree, is_leaf
+#[test]
+fn louds_navigation_random() {
+    for n in [1, 2, 10, 1000, 50_000] {
+        let parents = random_parents(n);
+        let tree = Louds::from_parents(&parents);
+        let mut children: Vec<Vec<usize>> = vec![Vec::new(); n];
+        for (v, p) in parents.iter().enumerate() {
+            if let Some(p) = p {
+                children[*p].push(v);
+            }
+        }
+
+        for v in 0..n {
+            let b = tree.get_bfs_id(v);
+            assert_eq!(tree.get_node_id(b), v);
+            assert_eq!(tree.parent(b), parents[v].map(|p| tree.get_bfs_id(p)));
+            assert_eq!(tree.degree(b), children[v].len());
+            assert_eq!(tree.is_leaf(b), children[v].is_empty());
+            assert_eq!(tree.first_child(b), children[v].first().map(|c| tree.get_bfs_id(*c)));
+            for (i, c) in children[v].iter().enumerate() {
+                assert_eq!(tree.child(b, i), tree.get_bfs_id(*c));
+                let next = children[v].get(i+1).map(|s| tree.get_bfs_id(*s));
+                assert_eq!(tree.next_sibling(tree.get_bfs_id(*c)), next);
+            }
+        }
+    }
+}
+
+// fn: from_parents, from_degrees
+#[test]
+fn louds_same_bits() {
+    let parents = random_parents(5000);
+    let a = Louds::from_parents(&parents);
+    let degrees: Vec<usize> = (0..a.len()).map(|v| a.degree(v)).collect();
+    let b = Louds::from_degrees(&degrees);
+    assert_eq!(a.get_bv().get_data(), b.get_bv().get_data());
+    assert!(a.size_in_bytes() > b.size_in_bytes());
+    assert_eq!(b.structure_tree().get_children().len(), 1);
+}
+
+// fn: child
+#[test]
+#[should_panic]
+fn louds_child_out_of_bounds() {
+    let tree = Louds::from_degrees(&[2,0,0]);
+    tree.child(0, 2);
+}
+
+// fn: parent
+#[test]
+#[should_panic]
+fn louds_parent_out_of_bounds() {
+    let tree = Louds::from_degrees(&[2,0,0]);
+    tree.parent(3);
+}
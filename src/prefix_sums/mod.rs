@@ -0,0 +1,192 @@
+use std::vec::Vec;
+use crate::int_vector::IntVector;
+use crate::structure::StructureNode;
+
+#[cfg(test)]
+mod tests;
+
+/// Compact prefix sum support for `IntVector`, that allows O(log n) time prefix sum queries.
+/// The structure consist of two level precomputed sums, similar to `RankSupport`.
+/// First block level stores sums of values in ranges `[0,k*b1-1]`, where `b1=log_2(n)^2`.
+///
+/// The second level stores relative sums inside the first level blocks,
+/// such that `block_level2[j] = sum(0, j*b2-1) - block_level1[j*b2/b1]`, where `b2=log_2(n)`.
+///
+/// ```
+/// use halko_rust::int_vector::IntVector;
+/// use halko_rust::prefix_sums::PrefixSums;
+///
+/// let iv = IntVector::from_slice(&[3,1,0,10,2]);
+///
+/// let ps = PrefixSums::new(iv);
+///
+/// assert_eq!(ps.prefix_sum(0), 3);
+/// assert_eq!(ps.prefix_sum(3), 14);
+/// assert_eq!(ps.range_sum(1, 3), 11);
+/// assert_eq!(ps.search(5), Some(3));
+/// ```
+pub struct PrefixSums {
+    iv: IntVector,
+    block_level1: IntVector,
+    block_level2: IntVector,
+    b1: usize,
+    b2: usize,
+}
+
+impl PrefixSums {
+    /// Builds prefix sum support for `iv`.
+    /// Function panics if the sum of all values does not fit into `u64`.
+    pub fn new(iv: IntVector) -> PrefixSums {
+        let n = iv.len();
+        let b2 = if n < 2 {1} else {n.ilog2() as usize};
+        let b1 = b2*b2;
+
+        let mut v1: Vec<u64> = Vec::with_capacity(n/b1 + 1);
+        let mut v2: Vec<u64> = Vec::with_capacity(n/b2 + 1);
+        let mut sum: u64 = 0;
+        let mut block_start: u64 = 0;
+        for i in 0..n {
+            if i % b1 == 0 {
+                v1.push(sum);
+                block_start = sum;
+            }
+            if i % b2 == 0 {
+                v2.push(sum - block_start);
+            }
+            sum = match sum.checked_add(iv.get(i)) {
+                Some(s) => s,
+                None => panic!("[PrefixSums::new], Sum of values does not fit into u64."),
+            };
+        }
+
+        PrefixSums {
+            iv,
+            block_level1: IntVector::from_slice(&v1),
+            block_level2: IntVector::from_slice(&v2),
+            b1,
+            b2,
+        }
+    }
+
+    pub fn get_iv(&self) -> &IntVector {
+        &self.iv
+    }
+
+    pub fn get_block_level1(&self) -> &IntVector {
+        &self.block_level1
+    }
+
+    pub fn get_block_level2(&self) -> &IntVector {
+        &self.block_level2
+    }
+
+    /// Returns the size of the structure in bytes, including the values and both block levels.
+    pub fn size_in_bytes(&self) -> usize {
+        self.iv.size_in_bytes() + self.block_level1.size_in_bytes()
+            + self.block_level2.size_in_bytes() + 16
+    }
+
+    /// Returns the space breakdown of the structure.
+    pub fn structure_tree(&self) -> StructureNode {
+        StructureNode::new("PrefixSums", self.size_in_bytes())
+            .with_field("b1", self.b1 as u64)
+            .with_field("b2", self.b2 as u64)
+            .with_child(self.iv.structure_tree().with_name("iv"))
+            .with_child(self.block_level1.structure_tree().with_name("block_level1"))
+            .with_child(self.block_level2.structure_tree().with_name("block_level2"))
+    }
+
+    /// Returns sum of values in range `[0,i]`.
+    /// Function panics if `i` is out of bounds.
+    pub fn prefix_sum(&self, i: usize) -> u64 {
+        if i >= self.iv.len() {
+            panic!("[PrefixSums::prefix_sum], Index out of bounds, i:{}, IntVector length:{}",
+                   i, self.iv.len());
+        }
+
+        let k2 = i/self.b2;
+        let scan_sum: u64 = (k2*self.b2..=i).map(|j| self.iv.get(j)).sum();
+        self.block_level1.get(i/self.b1) + self.block_level2.get(k2) + scan_sum
+    }
+
+    /// Returns sum of values in range `[a,b]`.
+    ///
+    /// ```
+    /// use halko_rust::int_vector::IntVector;
+    /// use halko_rust::prefix_sums::PrefixSums;
+    ///
+    /// let iv = IntVector::from_slice(&[1,2,3,4]);
+    /// let ps = PrefixSums::new(iv);
+    ///
+    /// assert_eq!(ps.range_sum(0, 3), 10);
+    /// assert_eq!(ps.range_sum(2, 2), 3);
+    /// assert_eq!(ps.range_sum(1, 2), 5);
+    /// ```
+    pub fn range_sum(&self, a: usize, b: usize) -> u64 {
+        if a > b {
+            panic!("[PrefixSums::range_sum], Invalid range, a:{}, b:{}", a, b);
+        }
+        let before = if a == 0 {0} else {self.prefix_sum(a-1)};
+        self.prefix_sum(b) - before
+    }
+
+    /// Returns the smallest index `i`, such that the sum of values in range `[0,i]` is at least
+    /// `x`, or `None` if the sum of all values is less than `x`.
+    ///
+    /// ```
+    /// use halko_rust::int_vector::IntVector;
+    /// use halko_rust::prefix_sums::PrefixSums;
+    ///
+    /// let iv = IntVector::from_slice(&[0,2,0,5]);
+    /// let ps = PrefixSums::new(iv); // sums: [0,2,2,7]
+    ///
+    /// assert_eq!(ps.search(0), Some(0));
+    /// assert_eq!(ps.search(1), Some(1));
+    /// assert_eq!(ps.search(3), Some(3));
+    /// assert_eq!(ps.search(8), None);
+    /// ```
+    pub fn search(&self, x: u64) -> Option<usize> {
+        let n = self.iv.len();
+        if n == 0 {
+            return None;
+        }
+        if x == 0 {
+            return Some(0);
+        }
+
+        // last level 1 block starting with sum less than x
+        let k1 = last_less_than(&self.block_level1, 0, self.block_level1.len(), x);
+        let rest = x - self.block_level1.get(k1);
+
+        // last level 2 block inside the level 1 block starting with relative sum less than rest
+        let start = k1*self.b1/self.b2;
+        let stop = self.block_level2.len().min(start + self.b1/self.b2);
+        let k2 = last_less_than(&self.block_level2, start, stop, rest);
+
+        let mut sum = self.block_level1.get(k1) + self.block_level2.get(k2);
+        for i in k2*self.b2..n {
+            sum += self.iv.get(i);
+            if sum >= x {
+                return Some(i);
+            }
+        }
+        None
+    }
+}
+
+/// Returns the last index `k` in range `[start,stop-1]`, such that `iv[k] < x`.
+/// Assumes that `iv[start] < x` and values are sorted.
+fn last_less_than(iv: &IntVector, start: usize, stop: usize, x: u64) -> usize {
+    let mut low = start;
+    let mut high = stop;
+    while high - low > 1 {
+        let mid = low + (high-low)/2;
+        if iv.get(mid) < x {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    low
+}
+
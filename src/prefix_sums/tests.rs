@@ -0,0 +1,105 @@
+use std::vec::Vec;
+use rand::{Rng, thread_rng};
+
+use crate::prefix_sums::PrefixSums;
+use crate::int_vector::IntVector;
+use crate::int_vector::test_utils::random_int_vector;
+
+fn naive_sums(iv: &IntVector) -> Vec<u64> {
+    let mut sums = Vec::new();
+    let mut sum = 0;
+    for i in 0..iv.len() {
+        sum += iv.get(i);
+        sums.push(sum);
+    }
+    sums
+}
+
+// fn: new
+#[test]
+fn new_block_levels() {
+    // n=16 -> b2=4, b1=16
+    let mut iv = IntVector::new(16, 2);
+    for i in 0..16 { iv.set(i, 1); }
+    let ps = PrefixSums::new(iv);
+
+    let mut res_level1 = IntVector::new(1, 1);
+    res_level1.set(0, 0);
+    let mut res_level2 = IntVector::new(4, 4);
+    res_level2.set(1, 4);
+    res_level2.set(2, 8);
+    res_level2.set(3, 12);
+
+    assert_eq!(*ps.get_block_level1(), res_level1);
+    assert_eq!(*ps.get_block_level2(), res_level2);
+}
+
+// fn: new
+#[test]
+#[should_panic]
+fn new_sum_overflow() {
+    let mut iv = IntVector::new(2, 64);
+    iv.set(0, u64::MAX);
+    iv.set(1, 1);
+    let _ps = PrefixSums::new(iv);
+}
+
+// fn: prefix_sum
+#[test]
+fn prefix_sum_all_sizes() {
+    for n in 1..300 {
+        let iv = random_int_vector(n, 6);
+        let sums = naive_sums(&iv);
+        let ps = PrefixSums::new(iv);
+        for (i, s) in sums.iter().enumerate() {
+            assert_eq!(ps.prefix_sum(i), *s, "n:{}, i:{}", n, i);
+        }
+    }
+}
+
+// fn: prefix_sum
+#[test]
+#[should_panic]
+fn prefix_sum_out_of_bounds() {
+    let ps = PrefixSums::new(IntVector::new(5, 3));
+    ps.prefix_sum(5);
+}
+
+// fn: range_sum
+#[test]
+fn range_sum_random() {
+    let mut rng = thread_rng();
+    let iv = random_int_vector(1000, 20);
+    let v: Vec<u64> = iv.to_vec();
+    let ps = PrefixSums::new(iv);
+    for _ in 0..500 {
+        let a = rng.gen_range(0..v.len());
+        let b = rng.gen_range(a..v.len());
+        assert_eq!(ps.range_sum(a, b), v[a..=b].iter().sum::<u64>());
+    }
+}
+
+// fn: search
+#[test]
+fn search_all_sizes() {
+    for n in 1..200 {
+        let iv = random_int_vector(n, 3);
+        let sums = naive_sums(&iv);
+        let ps = PrefixSums::new(iv);
+        for x in 0..=sums[n-1]+1 {
+            let res = sums.iter().position(|s| *s >= x);
+            assert_eq!(ps.search(x), res, "n:{}, x:{}", n, x);
+        }
+    }
+}
+
+// fn: search
+#[test]
+fn search_empty_and_zeros() {
+    let ps = PrefixSums::new(IntVector::new(0, 3));
+    assert_eq!(ps.search(0), None);
+
+    let ps = PrefixSums::new(IntVector::new(100, 3));
+    assert_eq!(ps.search(0), Some(0));
+    assert_eq!(ps.search(1), None);
+}
@@ -1,6 +1,9 @@
 use std::vec::Vec;
+use std::thread;
+use std::borrow::{Borrow, BorrowMut};
 use crate::bitvectors::{Bitvector, Bit};
-use crate::int_vector::IntVector;
+use crate::int_vector::{IntVector, bit_length};
+use crate::structure::StructureNode;
 
 #[cfg(test)]
 mod tests;
@@ -12,84 +15,128 @@ mod tests;
 ///
 /// The second level computes relative rank values between two blocks in the first block level.
 /// Such that `block_level2[i] = rank1(bv, i*b2) - block_level1[i*b2/b1]`, where `b2=log_2(|bv|)`.
-pub struct RankSupport {
-    bv: Bitvector,
+///
+/// The bitvector can be owned (`Bitvector`), borrowed (`&Bitvector`) or shared (`Arc<Bitvector>`),
+/// or stored in any other type implementing `Borrow<Bitvector>`. Borrowing allows several support
+/// structures on one bitvector, and owned bitvector can be taken back with `into_inner`.
+///
+/// ```
+/// use std::sync::Arc;
+/// use halko_rust::bitvectors::Bitvector;
+/// use halko_rust::rank_support::RankSupport;
+///
+/// let bv = Bitvector::build(&[0,1,0,0,1,1,0]);
+///
+/// let rs1 = RankSupport::new(&bv);
+/// let rs2 = RankSupport::new(&bv);
+/// assert_eq!(rs1.rank1(4), rs2.rank1(4));
+///
+/// let shared = Arc::new(bv);
+/// let rs3 = RankSupport::new(Arc::clone(&shared));
+/// assert_eq!(rs3.rank1(6), 3);
+/// ```
+pub struct RankSupport<B = Bitvector> {
+    bv: B,
     block_level1: IntVector,
     block_level2: IntVector,
     b1: usize,
     b2: usize,
 }
 
-impl RankSupport {
+impl<B: Borrow<Bitvector>> RankSupport<B> {
 
-    pub fn new(bv: Bitvector) -> RankSupport {
-        let b1 = bv.len().ilog2().pow(2) as usize;
-        let b1 = if b1 > bv.len() {0} else {b1}; // this happens only with low
-                                                 // values
-        let b2 = bv.len().ilog2() as usize;
+    pub fn new(bv: B) -> RankSupport<B> {
+        let (b1, b2) = block_sizes(bv.borrow().len());
+        RankSupport::build(bv, b1, b2, 1)
+    }
 
-        let b1_n = if b1 == 0 {1} else {(bv.len()+b1-1)/b1}; // ceil(bv.len()/b1)
-        let b2_n = if b2 == 0 {1} else {(bv.len()+b2-1)/b2}; // ceil(bv.len()/b2)
+    /// Builds the same structure as `RankSupport::new`, but counts the blocks with `threads`
+    /// threads. The bitvector is split into chunks of consecutive `block_level1` blocks, each
+    /// chunk is counted by its own thread, and the absolute values of `block_level1` are
+    /// computed from the chunk counts afterwards.
+    ///
+    /// ```
+    /// use halko_rust::bitvectors::Bitvector;
+    /// use halko_rust::rank_support::RankSupport;
+    ///
+    /// let a: Vec<u32> = (0..10_000).map(|i| (i % 3 == 0) as u32).collect();
+    /// let rs = RankSupport::new_parallel(Bitvector::build(&a), 4);
+    /// let rs_seq = RankSupport::new(Bitvector::build(&a));
+    ///
+    /// assert_eq!(rs.get_block_level1(), rs_seq.get_block_level1());
+    /// assert_eq!(rs.get_block_level2(), rs_seq.get_block_level2());
+    /// assert_eq!(rs.rank1(9_999), 3334);
+    /// ```
+    pub fn new_parallel(bv: B, threads: usize) -> RankSupport<B> {
+        let (b1, b2) = block_sizes(bv.borrow().len());
+        RankSupport::build(bv, b1, b2, threads)
+    }
 
-        // finding length of block_level1
-        let rank_last = bv.rank1(b1*(b1_n-1));
-        let l1 = 64-rank_last.leading_zeros() as usize;
-        let mut v1 = IntVector::new(b1_n, if l1 == 0 {1} else {l1});
+    fn build(bv: B, b1: usize, b2: usize, threads: usize) -> RankSupport<B> {
+        let bits: &Bitvector = bv.borrow();
+        let b1_n = if b1 == 0 {1} else {bits.len().div_ceil(b1)};
+        let b2_n = if b2 == 0 {1} else {bits.len().div_ceil(b2)};
 
+        // counts[i] = (number of 1s in the i-th level 1 block, relative level 2 values of the block)
+        let threads = threads.clamp(1, b1_n.max(1));
+        let chunk = b1_n.div_ceil(threads);
+        let counts: Vec<(u64, Vec<u64>)> = if bits.is_empty() {
+            vec![(0, Vec::new())]
+        } else if threads == 1 {
+            (0..b1_n).map(|i| scan_block_level1(bits, i, b1, b2)).collect()
+        } else {
+            thread::scope(|s| {
+                let handles: Vec<_> = (0..threads).map(|t| {
+                    s.spawn(move || {
+                        (t*chunk..b1_n.min((t+1)*chunk))
+                            .map(|i| scan_block_level1(bits, i, b1, b2))
+                            .collect::<Vec<_>>()
+                    })
+                }).collect();
+                handles.into_iter().flat_map(|h| h.join().unwrap()).collect()
+            })
+        };
 
+        let mut v1 = vec![0; b1_n];
         let mut v2 = vec![0; b2_n];
-
-
-        // first block_level2 values inside first value in block_level1
-        // if b2=0, then there are not block in level 2
-        if b2 > 0 {
-            let k = if b1 <= bv.len() {b1/b2} else {(bv.len()%b1)+b2-1/b2};
-            for j in 1..k {
-                let start = (j-1)*b2;
-                let stop = start+b2-1;
-                let b2_i = j;
-                v2[b2_i] = v2[b2_i-1] + bv.scan_blocks(start, stop, Bit::ONE, u64::MAX).0;
+        let b2_per_b1 = if b1 == 0 || b2 == 0 {0} else {b1/b2};
+        for (i, (_, rel)) in counts.iter().enumerate() {
+            if i > 0 {
+                v1[i] = v1[i-1] + counts[i-1].0;
             }
-        }
-
-        for i in 1..b1_n {
-            v1.set(i, v1.get(i-1) + bv.scan_blocks((i-1)*b1, i*b1-1, Bit::ONE, u64::MAX).0);
-
-
-            let k = if i*b1 + b1 <= bv.len() {b1/b2} else {((bv.len()%b1)+b2-1)/b2};
-            for j in 1..k {
-                let start = i*b1 + (j-1)*b2;
-                let stop = start+b2-1;
-                let b2_i = i*b2+j;
-                //println!("j:{}, i:{}, k:{}", j,i,k);
-                v2[b2_i] = v2[b2_i-1] + bv.scan_blocks(start, stop, Bit::ONE, u64::MAX).0;
+            for (j, x) in rel.iter().enumerate() {
+                v2[i*b2_per_b1 + j + 1] = *x;
             }
         }
 
-        // finding length  of v2 block
-        let v2_max = v2.iter().max();
-        let l2 = match v2_max {
-            Some(x) => if *x == 0 {1} else {64-x.leading_zeros() as usize},
-            None => 1,
-        };
-
-        let mut v2_iv = IntVector::new(v2.len(), l2);
-        for (i, x) in v2.iter().enumerate() {
-            v2_iv.set(i, *x);
-        }
-
-
         RankSupport {
             bv,
-            block_level1: v1,
-            block_level2: v2_iv,
+            block_level1: IntVector::from_slice(&v1),
+            block_level2: IntVector::from_slice(&v2),
             b1,
             b2,
         }
     }
 
     pub fn get_bv(&self) -> &Bitvector {
-        &self.bv
+        self.bv.borrow()
+    }
+
+    /// Consumes the rank support and returns the bitvector storage.
+    ///
+    /// ```
+    /// use halko_rust::bitvectors::{Bitvector, Bit};
+    /// use halko_rust::rank_support::RankSupport;
+    ///
+    /// let rs = RankSupport::new(Bitvector::build(&[0,1,1]));
+    /// let mut bv = rs.into_inner();
+    /// bv.set(0, Bit::ONE);
+    ///
+    /// let rs = RankSupport::new(bv);
+    /// assert_eq!(rs.rank1(2), 3);
+    /// ```
+    pub fn into_inner(self) -> B {
+        self.bv
     }
 
     pub fn get_block_level1(&self) -> &IntVector {
@@ -100,6 +147,77 @@ impl RankSupport {
         &self.block_level2
     }
 
+    /// Returns block sizes `(b1,b2)` of the first and second block level.
+    pub fn get_block_sizes(&self) -> (usize, usize) {
+        (self.b1, self.b2)
+    }
+
+    /// Returns range `[start,stop)` of `block_level2` indices inside the `k`-th `block_level1` block.
+    fn block_level2_range(&self, k: usize) -> (usize, usize) {
+        let n2 = self.block_level2.len();
+        if self.b1 == 0 {
+            return (0, n2);
+        }
+        let per_block = self.b1/self.b2;
+        (k*per_block, n2.min((k+1)*per_block))
+    }
+
+    /// Returns the number of bits used by the block levels per bit of the bitvector.
+    ///
+    /// ```
+    /// use halko_rust::bitvectors::Bitvector;
+    /// use halko_rust::rank_support::{RankSupport, RankSupportBuilder};
+    ///
+    /// let bv = Bitvector::build_empty(1 << 16);
+    /// let fast = RankSupportBuilder::fast().build(&bv);
+    /// let compact = RankSupportBuilder::compact().build(&bv);
+    ///
+    /// assert!(compact.space_overhead() < fast.space_overhead());
+    /// ```
+    pub fn space_overhead(&self) -> f64 {
+        let bits = self.block_level1.len()*self.block_level1.get_l()
+            + self.block_level2.len()*self.block_level2.get_l();
+        bits as f64 / self.get_bv().len().max(1) as f64
+    }
+
+    /// Returns the size of the structure in bytes, including the bitvector and both block levels.
+    /// The bitvector is counted also when it is borrowed or shared, e.g. for `RankSupport<&Bitvector>`
+    /// or `RankSupport<Arc<Bitvector>>`, so the size of the block levels alone is
+    /// `size_in_bytes() - get_bv().size_in_bytes()`.
+    ///
+    /// ```
+    /// use halko_rust::bitvectors::Bitvector;
+    /// use halko_rust::rank_support::RankSupport;
+    ///
+    /// let bv = Bitvector::build_empty(1 << 16);
+    /// let rs = RankSupport::new(&bv);
+    ///
+    /// assert!(rs.size_in_bytes() > bv.size_in_bytes());
+    /// ```
+    pub fn size_in_bytes(&self) -> usize {
+        self.get_bv().size_in_bytes() + self.block_level1.size_in_bytes()
+            + self.block_level2.size_in_bytes() + 16
+    }
+
+    /// Returns the space breakdown of the structure. As in `size_in_bytes`, the bitvector is
+    /// reported as child `bv` and included in the total also when it is borrowed or shared.
+    ///
+    /// ```
+    /// use halko_rust::bitvectors::Bitvector;
+    /// use halko_rust::rank_support::RankSupport;
+    ///
+    /// let rs = RankSupport::new(Bitvector::build_empty(1 << 16));
+    /// println!("{}", rs.structure_tree().to_text());
+    /// ```
+    pub fn structure_tree(&self) -> StructureNode {
+        StructureNode::new("RankSupport", self.size_in_bytes())
+            .with_field("b1", self.b1 as u64)
+            .with_field("b2", self.b2 as u64)
+            .with_child(self.get_bv().structure_tree().with_name("bv"))
+            .with_child(self.block_level1.structure_tree().with_name("block_level1"))
+            .with_child(self.block_level2.structure_tree().with_name("block_level2"))
+    }
+
     /// Returns numbers of 1s in O(1) time from the bitvector in range `[0,i]`.
     ///
     /// ```
@@ -124,13 +242,13 @@ impl RankSupport {
     pub fn rank1(&self, i: usize) -> u64 {
 
         //println!("i:{}, {}, {}, {}, {}, {}", i, self._block_level1.len(), self._b1_size, i/self._b1_size, self._block_level2.len(), i/self._b2_size);
-        let k1 = if self.b1 == 0 {0} else {i/self.b1};
+        let k1 = i.checked_div(self.b1).unwrap_or(0);
         let b1_sum = self.block_level1.get(k1);
 
-        let k2 = if self.b2 == 0 {0} else {i/self.b2};
+        let k2 = i.checked_div(self.b2).unwrap_or(0);
         let b2_sum = self.block_level2.get(k2);
 
-        let scan_sum = self.bv.scan_blocks(k2*self.b2, i, Bit::ONE, u64::MAX).0;
+        let scan_sum = self.get_bv().scan_blocks(k2*self.b2, i, Bit::ONE, u64::MAX).0;
         //println!("b1:{}, b2:{}, scan_sum: {}", b1_sum, b2_sum, scan_sum);
 
         b1_sum+b2_sum+scan_sum
@@ -162,4 +280,323 @@ impl RankSupport {
         (i as u64+1) - self.rank1(i)
     }
 
+    /// Returns index of the `k`-th 1bit in the bitvector. The block levels are binary searched,
+    /// and the last block is scanned word by word.
+    /// Function panics if `k=0` or if the bitvector has less than `k` 1bits.
+    ///
+    /// ```
+    /// use std::panic;
+    /// use halko_rust::bitvectors::Bitvector;
+    /// use halko_rust::rank_support::RankSupport;
+    ///
+    /// let a: [u32; 7] = [0,1,0,0,1,1,0];
+    /// let rs = RankSupport::new(Bitvector::build(&a));
+    ///
+    /// assert_eq!(rs.select1(1), 1);
+    /// assert_eq!(rs.select1(2), 4);
+    /// assert_eq!(rs.select1(3), 5);
+    ///
+    /// let panic_result = panic::catch_unwind(|| {
+    ///     rs.select1(4)
+    /// });
+    /// assert!(panic_result.is_err());
+    /// ```
+    pub fn select1(&self, k: usize) -> usize {
+        self.select(k, true)
+    }
+
+    /// Returns index of the `k`-th 0bit in the bitvector.
+    /// Function panics if `k=0` or if the bitvector has less than `k` 0bits.
+    ///
+    /// ```
+    /// use halko_rust::bitvectors::Bitvector;
+    /// use halko_rust::rank_support::RankSupport;
+    ///
+    /// let a: [u32; 7] = [0,1,0,0,1,1,0];
+    /// let rs = RankSupport::new(Bitvector::build(&a));
+    ///
+    /// assert_eq!(rs.select0(1), 0);
+    /// assert_eq!(rs.select0(2), 2);
+    /// assert_eq!(rs.select0(4), 6);
+    /// ```
+    pub fn select0(&self, k: usize) -> usize {
+        self.select(k, false)
+    }
+
+    fn select(&self, k: usize, ones: bool) -> usize {
+        let bits = self.get_bv();
+        let n = bits.len();
+        let total = if n == 0 {0} else if ones {self.rank1(n-1)} else {self.rank0(n-1)};
+        if k == 0 || k as u64 > total {
+            panic!("[RankSupport::select], Invalid select query, k:{}, number of {}bits:{}",
+                   k, if ones {1} else {0}, total);
+        }
+        let k = k as u64;
+
+        // number of searched bits before position `pos`, when there are `ones_before` 1bits
+        let before = |pos: usize, ones_before: u64| if ones {ones_before} else {pos as u64 - ones_before};
+
+        // last level 1 block starting with less than k searched bits
+        let (mut lo, mut hi) = (0, self.block_level1.len());
+        while hi - lo > 1 {
+            let mid = (lo+hi)/2;
+            if before(mid*self.b1, self.block_level1.get(mid)) < k {lo = mid} else {hi = mid}
+        }
+        let j = lo;
+        let ones1 = self.block_level1.get(j);
+
+        // last level 2 block inside the level 1 block starting with less than k searched bits
+        let (start, stop) = self.block_level2_range(j);
+        let (mut lo, mut hi) = (start, stop.max(start+1));
+        while hi - lo > 1 {
+            let mid = (lo+hi)/2;
+            if before(mid*self.b2, ones1 + self.block_level2.get(mid)) < k {lo = mid} else {hi = mid}
+        }
+        let mut pos = if self.b2 == 0 {j*self.b1} else {lo*self.b2};
+        let mut r = k - before(pos, ones1 + self.block_level2.get(lo));
+
+        loop {
+            let len = 64.min(n-pos);
+            let mut w = bits.get_int(pos, len);
+            if !ones {
+                w = !w & if len == 64 {u64::MAX} else {!(u64::MAX << len)};
+            }
+            let c = w.count_ones() as u64;
+            if c >= r {
+                for _ in 1..r {
+                    w &= w-1;
+                }
+                return pos + w.trailing_zeros() as usize;
+            }
+            r -= c;
+            pos += len;
+        }
+    }
+
+}
+
+impl<B: BorrowMut<Bitvector>> RankSupport<B> {
+    /// Sets or unsets the `i`-th bit of the bitvector and updates the block levels
+    /// in `O(|bv|/b1 + b1/b2)` time.
+    ///
+    /// ```
+    /// use halko_rust::bitvectors::{Bitvector, Bit};
+    /// use halko_rust::rank_support::RankSupport;
+    ///
+    /// let mut rs = RankSupport::new(Bitvector::build(&[0,1,0,0,1,1,0]));
+    /// assert_eq!(rs.rank1(6), 3);
+    ///
+    /// rs.set(0, Bit::ONE);
+    /// rs.set(5, Bit::ZERO);
+    /// assert_eq!(rs.rank1(4), 3);
+    /// assert_eq!(rs.rank1(6), 3);
+    /// ```
+    pub fn set(&mut self, i: usize, bit: Bit) {
+        let n = self.get_bv().len();
+        if i >= n {
+            panic!("[RankSupport::set], Index out of bounds, i:{}, length of bitvector:{}", i, n);
+        }
+
+        let new_bit = bit.value();
+        if self.get_bv().get(i) == new_bit {
+            return;
+        }
+        self.bv.borrow_mut().set(i, bit);
+
+        let k1 = i.checked_div(self.b1).unwrap_or(0);
+        for k in k1+1..self.block_level1.len() {
+            let x = self.block_level1.get(k);
+            let x = if new_bit == 1 {x+1} else {x-1};
+            set_widen(&mut self.block_level1, k, x);
+        }
+
+        if let Some(k2) = i.checked_div(self.b2) {
+            let (_, stop) = self.block_level2_range(k1);
+            for k in k2+1..stop {
+                let x = self.block_level2.get(k);
+                let x = if new_bit == 1 {x+1} else {x-1};
+                set_widen(&mut self.block_level2, k, x);
+            }
+        }
+    }
+
+    /// Applies all updates `(i,bit)` into the bitvector, and recomputes only the block level
+    /// values of the `block_level1` blocks containing updated bits.
+    ///
+    /// ```
+    /// use halko_rust::bitvectors::{Bitvector, Bit};
+    /// use halko_rust::rank_support::RankSupport;
+    ///
+    /// let mut rs = RankSupport::new(Bitvector::build_empty(1000));
+    /// rs.apply_updates(vec![(10, Bit::ONE), (500, Bit::ONE), (999, Bit::ONE)]);
+    ///
+    /// assert_eq!(rs.rank1(9), 0);
+    /// assert_eq!(rs.rank1(500), 2);
+    /// assert_eq!(rs.rank1(999), 3);
+    /// ```
+    pub fn apply_updates<I: IntoIterator<Item = (usize, Bit)>>(&mut self, updates: I) {
+        let n = self.get_bv().len();
+        let mut dirty: Vec<usize> = Vec::new();
+        for (i, bit) in updates {
+            if i >= n {
+                panic!("[RankSupport::apply_updates], Index out of bounds, i:{}, length of bitvector:{}", i, n);
+            }
+            self.bv.borrow_mut().set(i, bit);
+            dirty.push(i.checked_div(self.b1).unwrap_or(0));
+        }
+        dirty.sort_unstable();
+        dirty.dedup();
+
+        let b1_n = self.block_level1.len();
+        let mut diff: i64 = 0; // change of 1s in the dirty blocks before the current block
+        let mut d = 0;
+        for k in dirty.first().copied().unwrap_or(b1_n)..b1_n {
+            if diff != 0 {
+                let x = (self.block_level1.get(k) as i64 + diff) as u64;
+                set_widen(&mut self.block_level1, k, x);
+            }
+
+            if d < dirty.len() && dirty[d] == k {
+                let (ones, rel) = scan_block_level1(self.get_bv(), k, self.b1, self.b2);
+                if k+1 < b1_n {
+                    // old number of 1s, the value of the next block is not updated yet
+                    let old = self.block_level1.get(k+1) as i64 + diff - self.block_level1.get(k) as i64;
+                    diff += ones as i64 - old;
+                }
+                if self.b2 > 0 {
+                    let (start, _) = self.block_level2_range(k);
+                    for (j, x) in rel.iter().enumerate() {
+                        set_widen(&mut self.block_level2, start+j+1, *x);
+                    }
+                }
+                d += 1;
+            }
+        }
+    }
+}
+
+impl RankSupport {
+    /// Returns builder for configuring the block sizes of `RankSupport`.
+    ///
+    /// ```
+    /// use halko_rust::bitvectors::Bitvector;
+    /// use halko_rust::rank_support::RankSupport;
+    ///
+    /// let bv = Bitvector::build(&[0,1,0,0,1,1,0]);
+    /// let rs = RankSupport::builder().superblock_bits(4).block_bits(2).build(bv);
+    ///
+    /// assert_eq!(rs.get_block_sizes(), (4, 2));
+    /// assert_eq!(rs.rank1(6), 3);
+    /// ```
+    pub fn builder() -> RankSupportBuilder {
+        RankSupportBuilder::default()
+    }
+}
+
+/// Builder for `RankSupport` with configurable block sizes.
+///
+/// Block sizes trade space for query time: rank query scans at most `block_bits` bits, and
+/// `block_level2` stores one value of `log_2(superblock_bits)` bits for each block.
+/// Presets:
+/// * `default`, `b1=log_2(n)^2` and `b2=log_2(n)` as in `RankSupport::new`
+/// * `fast`, word aligned blocks `b1=512` and `b2=64`
+/// * `compact`, `b1=4096` and `b2=256`
+#[derive(Debug, Clone, Default)]
+pub struct RankSupportBuilder {
+    superblock_bits: Option<usize>,
+    block_bits: Option<usize>,
+    threads: usize,
+}
+
+impl RankSupportBuilder {
+    /// Preset with word aligned blocks `b1=512` and `b2=64`.
+    pub fn fast() -> RankSupportBuilder {
+        RankSupportBuilder::default().superblock_bits(512).block_bits(64)
+    }
+
+    /// Preset with large blocks `b1=4096` and `b2=256`.
+    pub fn compact() -> RankSupportBuilder {
+        RankSupportBuilder::default().superblock_bits(4096).block_bits(256)
+    }
+
+    /// Sets the size of `block_level1` blocks in bits.
+    pub fn superblock_bits(mut self, b1: usize) -> RankSupportBuilder {
+        self.superblock_bits = Some(b1);
+        self
+    }
+
+    /// Sets the size of `block_level2` blocks in bits.
+    pub fn block_bits(mut self, b2: usize) -> RankSupportBuilder {
+        self.block_bits = Some(b2);
+        self
+    }
+
+    /// Sets the number of threads used for counting the blocks, see `RankSupport::new_parallel`.
+    pub fn threads(mut self, threads: usize) -> RankSupportBuilder {
+        self.threads = threads;
+        self
+    }
+
+    /// Builds `RankSupport` for `bv`.
+    /// Function panics if only one of the block sizes is set, if the block sizes are 0 or
+    /// if `superblock_bits` is not a multiple of `block_bits`.
+    pub fn build<B: Borrow<Bitvector>>(&self, bv: B) -> RankSupport<B> {
+        let (b1, b2) = match (self.superblock_bits, self.block_bits) {
+            (None, None) => block_sizes(bv.borrow().len()),
+            (Some(b1), Some(b2)) => {
+                if b2 == 0 || b1 == 0 || b1 % b2 != 0 {
+                    panic!("[RankSupportBuilder::build], Invalid block sizes, superblock_bits:{}, block_bits:{}. \
+                           Sizes must be greater than 0 and superblock_bits must be a multiple of block_bits.",
+                           b1, b2);
+                }
+                (b1, b2)
+            },
+            _ => panic!("[RankSupportBuilder::build], Both superblock_bits and block_bits must be set."),
+        };
+        RankSupport::build(bv, b1, b2, self.threads.max(1))
+    }
+}
+
+/// Returns block sizes `(b1,b2)`, where `b1=log_2(n)^2` and `b2=log_2(n)`.
+fn block_sizes(n: usize) -> (usize, usize) {
+    if n < 2 {
+        return (0, 0);
+    }
+    let b1 = n.ilog2().pow(2) as usize;
+    let b1 = if b1 > n {0} else {b1}; // this happens only with low
+                                      // values
+    let b2 = n.ilog2() as usize;
+    (b1, b2)
+}
+
+/// Counts the `i`-th level 1 block. Returns tuple `(m,v)`, where `m` is the number of 1s in
+/// the block and `v` contains relative level 2 values of the block, excluding the first
+/// value that is always 0. If `b1=0`, then the level 2 blocks cover the whole bitvector.
+fn scan_block_level1(bv: &Bitvector, i: usize, b1: usize, b2: usize) -> (u64, Vec<u64>) {
+    let len = if b1 == 0 {bv.len()} else {b1};
+    let start = i*len;
+    let stop = bv.len().min(start+len) - 1;
+    let ones = bv.scan_blocks(start, stop, Bit::ONE, u64::MAX).0;
+
+    // if b2=0, then there are not block in level 2
+    let mut rel = Vec::new();
+    if b2 > 0 {
+        let k = (stop-start+1).div_ceil(b2);
+        let mut sum = 0;
+        for j in 1..k {
+            let start = start + (j-1)*b2;
+            sum += bv.scan_blocks(start, start+b2-1, Bit::ONE, u64::MAX).0;
+            rel.push(sum);
+        }
+    }
+    (ones, rel)
+}
+
+/// Sets the `k`-th value of `iv` to `x`, and expands the width of `iv` if `x` does not fit.
+fn set_widen(iv: &mut IntVector, k: usize, x: u64) {
+    let l = bit_length(x);
+    if l > iv.get_l() {
+        iv.expand_width(l);
+    }
+    iv.set(k, x);
 }
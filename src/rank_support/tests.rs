@@ -727,3 +727,344 @@ fn rs_rank0_random2() {
         );
     }
 }
+
+// fn: rank1
+#[test]
+fn rs_rank1_without_block_level1() {
+    // |bv|=8 -> log_2(8)^2 > 8, so only block_level2 is used
+    let v = vec![1,1,0,1,1,1,0,1];
+    let bv = Bitvector::build_from_vec2(&v);
+    let rs = RankSupport::new(Bitvector::build_from_vec2(&v));
+
+    for i in 0..v.len() {
+        assert_eq!(rs.rank1(i), bv.rank1(i));
+    }
+}
+
+// fn: new_parallel
+#[test]
+fn new_parallel_equals_new_all_sizes() {
+    let mut rng = thread_rng();
+    for n in 1..700 {
+        let v: Vec<u32> = (0..n).map(|_| rng.gen_range(0..=1)).collect();
+        let rs = RankSupport::new(Bitvector::build_from_vec2(&v));
+        for threads in [1, 2, 3, 8] {
+            let rs_par = RankSupport::new_parallel(Bitvector::build_from_vec2(&v), threads);
+            assert_eq!(*rs_par.get_block_level1(), *rs.get_block_level1(), "n:{}, threads:{}", n, threads);
+            assert_eq!(*rs_par.get_block_level2(), *rs.get_block_level2(), "n:{}, threads:{}", n, threads);
+        }
+    }
+}
+
+// fn: new_parallel, rank1
+#[test]
+fn new_parallel_rank1_random() {
+    let mut rng = thread_rng();
+    let n = 100_000;
+    let v: Vec<u32> = (0..n).map(|_| rng.gen_range(0..=1)).collect();
+    let bv = Bitvector::build_from_vec2(&v);
+    let rs = RankSupport::new_parallel(Bitvector::build_from_vec2(&v), 4);
+    for _ in 0..1000 {
+        let i = rng.gen_range(0..n);
+        assert_eq!(rs.rank1(i), bv.rank1(i));
+    }
+}
+
+// fn: new, rank1
+#[test]
+fn new_borrowed_bitvector_several_supports() {
+    let mut rng = thread_rng();
+    let v: Vec<u32> = (0..1000).map(|_| rng.gen_range(0..=1)).collect();
+    let bv = Bitvector::build_from_vec2(&v);
+
+    let rs1 = RankSupport::new(&bv);
+    let rs2 = RankSupport::new_parallel(&bv, 3);
+    for i in 0..v.len() {
+        assert_eq!(rs1.rank1(i), bv.rank1(i));
+        assert_eq!(rs2.rank1(i), bv.rank1(i));
+    }
+}
+
+// fn: size_in_bytes, structure_tree
+#[test]
+fn size_in_bytes_borrowed_bitvector() {
+    use std::sync::Arc;
+
+    let v: Vec<u32> = (0..5000).map(|i| (i % 3 == 0) as u32).collect();
+    let bv = Bitvector::build_from_vec2(&v);
+    let bv_size = bv.size_in_bytes();
+    let borrowed = RankSupport::new(&bv);
+    let shared = RankSupport::new(Arc::new(Bitvector::build_from_vec2(&v)));
+    let owned = RankSupport::new(Bitvector::build_from_vec2(&v));
+
+    // the bitvector is counted for every kind of storage
+    assert_eq!(borrowed.size_in_bytes(), owned.size_in_bytes());
+    assert_eq!(shared.size_in_bytes(), owned.size_in_bytes());
+    let tree = borrowed.structure_tree();
+    assert_eq!(tree.get_size_in_bytes(), borrowed.size_in_bytes());
+    assert_eq!(tree.get_children()[0].get_name(), "bv");
+    assert_eq!(tree.get_children()[0].get_size_in_bytes(), bv_size);
+    let blocks: usize = tree.get_children()[1..].iter().map(|c| c.get_size_in_bytes()).sum();
+    assert_eq!(borrowed.size_in_bytes() - bv_size, blocks + 16);
+}
+
+// fn: new, rank1
+#[test]
+fn new_shared_bitvector() {
+    use std::sync::Arc;
+    use std::thread;
+
+    let v: Vec<u32> = (0..5000).map(|i| (i % 7 == 0) as u32).collect();
+    let bv = Arc::new(Bitvector::build_from_vec2(&v));
+
+    let handles: Vec<_> = (0..4).map(|_| {
+        let bv = Arc::clone(&bv);
+        thread::spawn(move || RankSupport::new(bv).rank1(4999))
+    }).collect();
+    for h in handles {
+        assert_eq!(h.join().unwrap(), 715);
+    }
+}
+
+// fn: new, rank1
+#[test]
+fn new_boxed_bitvector() {
+    let v = vec![1,0,1,1,0];
+    let rs = RankSupport::new(Box::new(Bitvector::build_from_vec2(&v)));
+    assert_eq!(rs.rank1(4), 3);
+}
+
+// fn: into_inner
+#[test]
+fn into_inner_returns_same_bitvector() {
+    let v: Vec<u32> = vec![0,1,1,0,1,0,0,1];
+    let rs = RankSupport::new(Bitvector::build_from_vec2(&v));
+    let bv = rs.into_inner();
+    for (i, x) in v.iter().enumerate() {
+        assert_eq!(bv.get(i), *x);
+    }
+}
+
+// fn: builder, rank1
+#[test]
+fn builder_custom_block_sizes_random() {
+    use crate::rank_support::RankSupportBuilder;
+
+    let mut rng = thread_rng();
+    let sizes = [(4, 2), (8, 8), (64, 16), (512, 64), (4096, 256), (96, 32)];
+    for n in [1, 7, 63, 64, 65, 1000, 5000] {
+        let v: Vec<u32> = (0..n).map(|_| rng.gen_range(0..=1)).collect();
+        let bv = Bitvector::build_from_vec2(&v);
+        for (b1, b2) in sizes {
+            let rs = RankSupport::builder().superblock_bits(b1).block_bits(b2).build(&bv);
+            assert_eq!(rs.get_block_sizes(), (b1, b2));
+            for i in 0..n {
+                assert_eq!(rs.rank1(i), bv.rank1(i), "n:{}, b1:{}, b2:{}, i:{}", n, b1, b2, i);
+            }
+        }
+        for builder in [RankSupportBuilder::fast(), RankSupportBuilder::compact(), RankSupportBuilder::default().threads(4)] {
+            let rs = builder.build(&bv);
+            for i in 0..n {
+                assert_eq!(rs.rank1(i), bv.rank1(i));
+            }
+        }
+    }
+}
+
+// fn: builder
+#[test]
+fn builder_default_equals_new() {
+    let mut rng = thread_rng();
+    let v: Vec<u32> = (0..3000).map(|_| rng.gen_range(0..=1)).collect();
+    let rs = RankSupport::builder().build(Bitvector::build_from_vec2(&v));
+    let rs_new = RankSupport::new(Bitvector::build_from_vec2(&v));
+    assert_eq!(*rs.get_block_level1(), *rs_new.get_block_level1());
+    assert_eq!(*rs.get_block_level2(), *rs_new.get_block_level2());
+}
+
+// fn: builder
+#[test]
+#[should_panic]
+fn builder_block_bits_not_dividing_superblock_bits() {
+    let _rs = RankSupport::builder().superblock_bits(100).block_bits(64).build(Bitvector::build_empty(10));
+}
+
+// fn: builder
+#[test]
+#[should_panic]
+fn builder_only_superblock_bits_set() {
+    let _rs = RankSupport::builder().superblock_bits(512).build(Bitvector::build_empty(10));
+}
+
+// fn: new
+#[test]
+fn new_empty_bitvector() {
+    let rs = RankSupport::new(Bitvector::build_empty(0));
+    assert_eq!(rs.get_bv().len(), 0);
+}
+
+// fn: new_parallel, build
+#[test]
+fn build_empty_bitvector_presets() {
+    use crate::rank_support::RankSupportBuilder;
+
+    let builders = [RankSupportBuilder::default(), RankSupportBuilder::fast(),
+        RankSupportBuilder::compact(), RankSupportBuilder::fast().threads(4),
+        RankSupportBuilder::compact().threads(4)];
+    for builder in builders {
+        let rs = builder.build(Bitvector::build_empty(0));
+        assert_eq!(rs.get_bv().len(), 0);
+    }
+    let rs = RankSupport::new_parallel(Bitvector::build_empty(0), 4);
+    assert_eq!(rs.get_bv().len(), 0);
+}
+
+// fn: space_overhead
+#[test]
+fn space_overhead_presets() {
+    use crate::rank_support::RankSupportBuilder;
+
+    let bv = Bitvector::build_from_vec(&vec![u64::MAX; 1 << 12]);
+    let fast = RankSupportBuilder::fast().build(&bv);
+    let compact = RankSupportBuilder::compact().build(&bv);
+    // fast: level2 has one 9 bit value per 64 bits
+    assert!(fast.space_overhead() > 9.0/64.0);
+    assert!(compact.space_overhead() < 0.1);
+}
+
+// fn: set, rank1
+#[test]
+fn set_random_updates_all_block_sizes() {
+    use crate::rank_support::RankSupportBuilder;
+
+    let mut rng = thread_rng();
+    for n in [1, 8, 100, 1000, 3000] {
+        let v: Vec<u32> = (0..n).map(|_| rng.gen_range(0..=1)).collect();
+        for builder in [RankSupportBuilder::default(), RankSupportBuilder::fast(), RankSupportBuilder::compact()] {
+            let mut bv = Bitvector::build_from_vec2(&v);
+            let mut rs = builder.build(Bitvector::build_from_vec2(&v));
+            for _ in 0..200 {
+                let i = rng.gen_range(0..n);
+                if rng.gen_range(0..=1) == 1 {
+                    bv.set(i, Bit::ONE);
+                    rs.set(i, Bit::ONE);
+                } else {
+                    bv.set(i, Bit::ZERO);
+                    rs.set(i, Bit::ZERO);
+                }
+                let j = rng.gen_range(0..n);
+                assert_eq!(rs.rank1(j), bv.rank1(j), "n:{}, j:{}", n, j);
+            }
+            for j in 0..n {
+                assert_eq!(rs.rank1(j), bv.rank1(j));
+            }
+        }
+    }
+}
+
+// fn: set
+#[test]
+fn set_all_ones_widens_block_levels() {
+    // all zeros gives 1 bit wide block levels
+    let n = 5000;
+    let mut rs = RankSupport::new(Bitvector::build_empty(n));
+    for i in 0..n {
+        rs.set(i, Bit::ONE);
+    }
+    for i in 0..n {
+        assert_eq!(rs.rank1(i), i as u64 + 1);
+    }
+}
+
+// fn: set
+#[test]
+fn set_borrowed_mutable_bitvector() {
+    let mut bv = Bitvector::build_empty(100);
+    {
+        let mut rs = RankSupport::new(&mut bv);
+        rs.set(50, Bit::ONE);
+        assert_eq!(rs.rank1(99), 1);
+    }
+    assert_eq!(bv.get(50), 1);
+}
+
+// fn: apply_updates
+#[test]
+fn apply_updates_equals_rebuild() {
+    let mut rng = thread_rng();
+    for n in [1, 8, 77, 1000, 10_000] {
+        let v: Vec<u32> = (0..n).map(|_| rng.gen_range(0..=1)).collect();
+        let mut bv = Bitvector::build_from_vec2(&v);
+        let mut rs = RankSupport::new(Bitvector::build_from_vec2(&v));
+        for _ in 0..5 {
+            let updates: Vec<(usize, u32)> = (0..rng.gen_range(0..50)).map(|_| (rng.gen_range(0..n), rng.gen_range(0..=1))).collect();
+            for (i, b) in updates.iter() {
+                bv.set(*i, if *b == 1 {Bit::ONE} else {Bit::ZERO});
+            }
+            rs.apply_updates(updates.iter().map(|(i, b)| (*i, if *b == 1 {Bit::ONE} else {Bit::ZERO})));
+            for j in 0..n {
+                assert_eq!(rs.rank1(j), bv.rank1(j), "n:{}, j:{}", n, j);
+            }
+        }
+    }
+}
+
+// fn: apply_updates
+#[test]
+#[should_panic]
+fn apply_updates_out_of_bounds() {
+    let mut rs = RankSupport::new(Bitvector::build_empty(10));
+    rs.apply_updates(vec![(10, Bit::ONE)]);
+}
+
+// fn: select1, select0
+#[test]
+fn rs_select_random() {
+    let mut rng = thread_rng();
+    for n in [1, 2, 8, 63, 64, 65, 200, 1000, 4096, 50_000] {
+        for p in [0.05, 0.5, 0.95] {
+            let a: Vec<u32> = (0..n).map(|_| rng.gen_bool(p) as u32).collect();
+            let rs = RankSupport::new(Bitvector::build(&a));
+
+            let (mut k1, mut k0) = (0, 0);
+            for (i, x) in a.iter().enumerate() {
+                if *x == 1 {
+                    k1 += 1;
+                    assert_eq!(rs.select1(k1), i);
+                } else {
+                    k0 += 1;
+                    assert_eq!(rs.select0(k0), i);
+                }
+            }
+        }
+    }
+}
+
+// fn: select1
+#[test]
+fn rs_select_builder_block_sizes() {
+    let a: Vec<u32> = (0..10_000).map(|i| (i % 7 == 3) as u32).collect();
+    let bv = Bitvector::build(&a);
+    for rs in [RankSupport::builder().superblock_bits(512).block_bits(64).build(&bv),
+               RankSupport::builder().superblock_bits(4096).block_bits(256).build(&bv)] {
+        for k in 1..=1428 {
+            assert_eq!(rs.select1(k), 7*(k-1) + 3);
+        }
+    }
+}
+
+// fn: select1
+#[test]
+#[should_panic]
+fn rs_select1_zero() {
+    let rs = RankSupport::new(Bitvector::build(&[1,1,0]));
+    rs.select1(0);
+}
+
+// fn: select0
+#[test]
+#[should_panic]
+fn rs_select0_too_large() {
+    let rs = RankSupport::new(Bitvector::build(&[1,1,0]));
+    rs.select0(2);
+}
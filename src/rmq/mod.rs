@@ -0,0 +1,248 @@
+use std::vec::Vec;
+use crate::bitvectors::{Bitvector, Bit};
+use crate::int_vector::{IntVector, bit_length};
+use crate::rank_support::RankSupport;
+use crate::structure::StructureNode;
+
+#[cfg(test)]
+mod tests;
+
+const BLOCK: usize = 512; // bits per block
+const SUPERBLOCK: usize = 64; // blocks per superblock
+
+/// Succinct range minimum query structure. Returns the position of the leftmost minimum in any
+/// range `[a,b]` without accessing the values.
+///
+/// The values are encoded as balanced parentheses of `2n` bits: for each value from left to
+/// right, a `)` is written for each larger value removed from the stack of the Cartesian tree,
+/// and a `(` for the value itself. An open parenthesis is 1bit. The minimum of `[a,b]` is found
+/// from the rightmost minimum excess between the parentheses of `a` and `b`.
+///
+/// The excess minima are stored for blocks of 512 bits, and for superblocks of 64 blocks with a
+/// sparse table over the superblocks, so the directory takes o(n) bits.
+///
+/// ```
+/// use halko_rust::int_vector::IntVector;
+/// use halko_rust::rmq::Rmq;
+///
+/// let a = [5,2,4,7,2,8,1,3];
+/// let iv = IntVector::from_slice(&a);
+/// let rmq = Rmq::new(&iv);
+///
+/// assert_eq!(rmq.rmq(0, 7), 6);
+/// assert_eq!(rmq.rmq(0, 5), 1);
+/// assert_eq!(rmq.rmq(2, 5), 4);
+/// assert_eq!(rmq.rmq(3, 3), 3);
+/// ```
+pub struct Rmq {
+    bp: RankSupport,
+    block_min: IntVector, // minimum excess of each block
+    superblock_min: IntVector, // minimum excess of each superblock
+    sparse: Vec<IntVector>, // sparse[j][i] = rightmost superblock of minimum excess in [i,i+2^j)
+    n: usize,
+}
+
+impl Rmq {
+    /// Builds range minimum query structure of the values in `iv`.
+    pub fn new(iv: &IntVector) -> Rmq {
+        let n = iv.len();
+        let mut bv = Bitvector::build_empty(2*n);
+        let mut stack: Vec<u64> = Vec::new();
+        let mut pos = 0;
+        for i in 0..n {
+            let x = iv.get(i);
+            while stack.last().is_some_and(|top| *top > x) {
+                stack.pop();
+                pos += 1;
+            }
+            stack.push(x);
+            bv.set(pos, Bit::ONE);
+            pos += 1;
+        }
+
+        // minimum excess of each block, excess of position p is the excess of range [0,p]
+        let l = bit_length(n as u64);
+        let blocks = (2*n).div_ceil(BLOCK);
+        let mut block_min = IntVector::new(blocks, l);
+        let mut excess: u64 = 0;
+        for k in 0..blocks {
+            let mut min = u64::MAX;
+            for p in k*BLOCK..(2*n).min((k+1)*BLOCK) {
+                if bv.get(p) == 1 {excess += 1} else {excess -= 1}
+                min = min.min(excess);
+            }
+            block_min.set(k, min);
+        }
+
+        let superblocks = blocks.div_ceil(SUPERBLOCK);
+        let mut superblock_min = IntVector::new(superblocks, l);
+        for k in 0..superblocks {
+            let min = (k*SUPERBLOCK..blocks.min((k+1)*SUPERBLOCK)).map(|b| block_min.get(b)).min().unwrap();
+            superblock_min.set(k, min);
+        }
+
+        let lk = bit_length(superblocks as u64);
+        let mut sparse: Vec<IntVector> = Vec::new();
+        let mut level = IntVector::new(superblocks, lk);
+        for k in 0..superblocks {
+            level.set(k, k as u64);
+        }
+        let mut width = 1;
+        while 2*width <= superblocks {
+            let mut next = IntVector::new(superblocks - 2*width + 1, lk);
+            for k in 0..next.len() {
+                let a = level.get(k);
+                let b = level.get(k + width);
+                let m = if superblock_min.get(b as usize) <= superblock_min.get(a as usize) {b} else {a};
+                next.set(k, m);
+            }
+            sparse.push(level);
+            level = next;
+            width *= 2;
+        }
+        sparse.push(level);
+
+        Rmq {
+            bp: RankSupport::new(bv),
+            block_min,
+            superblock_min,
+            sparse,
+            n,
+        }
+    }
+
+    /// Returns the number of values.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Returns the balanced parentheses of the Cartesian tree, where `(` is 1bit.
+    pub fn get_bp(&self) -> &Bitvector {
+        self.bp.get_bv()
+    }
+
+    /// Returns the position of the leftmost minimum value in range `[a,b]`.
+    /// Function panics if `a>b` or `b>=n`.
+    pub fn rmq(&self, a: usize, b: usize) -> usize {
+        if a > b || b >= self.n {
+            panic!("[Rmq::rmq], Invalid range, a:{}, b:{}, Rmq length:{}", a, b, self.n);
+        }
+        if a == b {
+            return a;
+        }
+        let x = self.bp.select1(a+1);
+        let y = self.bp.select1(b+1);
+        let (min, z) = self.rightmost_min_excess(x, y);
+        if min >= self.excess(x) {
+            a
+        } else {
+            self.bp.rank1(z+1) as usize - 1
+        }
+    }
+
+    /// Returns the size of the structure in bytes.
+    pub fn size_in_bytes(&self) -> usize {
+        self.bp.size_in_bytes() + self.block_min.size_in_bytes() + self.superblock_min.size_in_bytes()
+            + self.sparse.iter().map(|iv| iv.size_in_bytes()).sum::<usize>() + 8
+    }
+
+    /// Returns the space breakdown of the structure.
+    pub fn structure_tree(&self) -> StructureNode {
+        let mut node = StructureNode::new("Rmq", self.size_in_bytes())
+            .with_field("n", self.n as u64)
+            .with_child(self.bp.structure_tree().with_name("bp"))
+            .with_child(self.block_min.structure_tree().with_name("block_min"))
+            .with_child(self.superblock_min.structure_tree().with_name("superblock_min"));
+        for (j, iv) in self.sparse.iter().enumerate() {
+            node = node.with_child(iv.structure_tree().with_name(&format!("sparse{}", j)));
+        }
+        node
+    }
+
+    // Excess of range [0,p].
+    fn excess(&self, p: usize) -> u64 {
+        2*self.bp.rank1(p) - (p as u64 + 1)
+    }
+
+    // Returns (minimum excess, rightmost position of the minimum) in range [x,y].
+    fn rightmost_min_excess(&self, x: usize, y: usize) -> (u64, usize) {
+        let (bx, by) = (x/BLOCK, y/BLOCK);
+        if bx == by {
+            return self.scan(x, y);
+        }
+
+        let mut best = self.scan(x, (bx+1)*BLOCK - 1);
+        if bx+1 < by {
+            let (min, k) = self.rightmost_min_block(bx+1, by-1);
+            if min <= best.0 {
+                best = self.scan(k*BLOCK, (k+1)*BLOCK - 1);
+            }
+        }
+        let last = self.scan(by*BLOCK, y);
+        if last.0 <= best.0 {
+            best = last;
+        }
+        best
+    }
+
+    // Returns (minimum excess, rightmost block of the minimum) in block range [p,q].
+    fn rightmost_min_block(&self, p: usize, q: usize) -> (u64, usize) {
+        let (sp, sq) = (p/SUPERBLOCK, q/SUPERBLOCK);
+        if sp == sq {
+            return self.scan_blocks(p, q);
+        }
+
+        let mut best = self.scan_blocks(p, (sp+1)*SUPERBLOCK - 1);
+        if sp+1 < sq {
+            let k = self.rightmost_min_superblock(sp+1, sq-1);
+            if self.superblock_min.get(k) <= best.0 {
+                best = self.scan_blocks(k*SUPERBLOCK, (k+1)*SUPERBLOCK - 1);
+            }
+        }
+        let last = self.scan_blocks(sq*SUPERBLOCK, q);
+        if last.0 <= best.0 {
+            best = last;
+        }
+        best
+    }
+
+    // Rightmost superblock of minimum excess in superblock range [p,q] from the sparse table.
+    fn rightmost_min_superblock(&self, p: usize, q: usize) -> usize {
+        let j = (q-p+1).ilog2() as usize;
+        let a = self.sparse[j].get(p) as usize;
+        let b = self.sparse[j].get(q + 1 - (1 << j)) as usize;
+        if self.superblock_min.get(b) <= self.superblock_min.get(a) {b} else {a}
+    }
+
+    // Returns (minimum excess, rightmost block of the minimum) in block range [p,q] by scanning
+    // the block minima.
+    fn scan_blocks(&self, p: usize, q: usize) -> (u64, usize) {
+        let mut best = (u64::MAX, p);
+        for k in p..=q.min(self.block_min.len()-1) {
+            let min = self.block_min.get(k);
+            if min <= best.0 {
+                best = (min, k);
+            }
+        }
+        best
+    }
+
+    // Returns (minimum excess, rightmost position of the minimum) in range [x,y] by scanning
+    // the bits.
+    fn scan(&self, x: usize, y: usize) -> (u64, usize) {
+        let y = y.min(self.get_bp().len()-1);
+        let mut excess = self.excess(x);
+        let mut best = (excess, x);
+        for p in x+1..=y {
+            if self.get_bp().get(p) == 1 {excess += 1} else {excess -= 1}
+            if excess <= best.0 {
+                best = (excess, p);
+            }
+        }
+        best
+    }
+}
@@ -0,0 +1,87 @@
+use std::vec::Vec;
+use rand::{Rng, thread_rng};
+
+use crate::rmq::Rmq;
+use crate::int_vector::IntVector;
+
+fn naive_rmq(a: &[u64], s: usize, e: usize) -> usize {
+    let mut best = s;
+    for i in s..=e {
+        if a[i] < a[best] {
+            best = i;
+        }
+    }
+    best
+}
+
+// fn: new
+#[test]
+fn rmq_new_bp() {
+    // 3: (, 1: )(, 2: (, 0: ))(, end: )
+    let rmq = Rmq::new(&IntVector::from_slice(&[3,1,2,0]));
+    let bits: Vec<u32> = (0..8).map(|i| rmq.get_bp().get(i)).collect();
+    assert_eq!(bits, vec![1,0,1,1,0,0,1,0]);
+    assert_eq!(rmq.len(), 4);
+}
+
+// fn: rmq
+#[test]
+fn rmq_small_all_ranges() {
+    let a = vec![4,4,2,7,2,2,9,0,0,5,1,4];
+    let rmq = Rmq::new(&IntVector::from_slice(&a));
+    for s in 0..a.len() {
+        for e in s..a.len() {
+            assert_eq!(rmq.rmq(s, e), naive_rmq(&a, s, e));
+        }
+    }
+}
+
+// fn: rmq
+#[test]
+fn rmq_monotone() {
+    let inc: Vec<u64> = (0..5000).collect();
+    let dec: Vec<u64> = (0..5000).rev().collect();
+    let rmq_inc = Rmq::new(&IntVector::from_slice(&inc));
+    let rmq_dec = Rmq::new(&IntVector::from_slice(&dec));
+    for (s, e) in [(0, 4999), (17, 4000), (2500, 2501), (1023, 1024)] {
+        assert_eq!(rmq_inc.rmq(s, e), s);
+        assert_eq!(rmq_dec.rmq(s, e), e);
+    }
+}
+
+// fn: rmq
+#[test]
+fn rmq_random() {
+    let mut rng = thread_rng();
+    for (n, sigma) in [(1000, 3), (1000, 1000), (100_000, 50), (100_000, 1 << 20)] {
+        let a: Vec<u64> = (0..n).map(|_| rng.gen_range(0..sigma)).collect();
+        let rmq = Rmq::new(&IntVector::from_slice(&a));
+        for _ in 0..500 {
+            let x = rng.gen_range(0..n);
+            let y = rng.gen_range(0..n);
+            let (s, e) = (x.min(y), x.max(y));
+            assert_eq!(rmq.rmq(s, e), naive_rmq(&a, s, e));
+        }
+    }
+}
+
+// fn: rmq
+#[test]
+fn rmq_space() {
+    let mut rng = thread_rng();
+    let n = 1 << 20;
+    let a: Vec<u64> = (0..n).map(|_| rng.gen_range(0..1000)).collect();
+    let rmq = Rmq::new(&IntVector::from_slice(&a));
+
+    // 2n bits and the rank support, with less than 0.1 bits per value for the directory
+    let directory = rmq.size_in_bytes() - rmq.structure_tree().get_children()[0].get_size_in_bytes();
+    assert!((directory*8) as f64 / (n as f64) < 0.1);
+}
+
+// fn: rmq
+#[test]
+#[should_panic]
+fn rmq_invalid_range() {
+    let rmq = Rmq::new(&IntVector::from_slice(&[1,2,3]));
+    rmq.rmq(2, 1);
+}
@@ -0,0 +1,201 @@
+use std::io::{self, Read, Write};
+use std::vec::Vec;
+use crate::bitvectors::Bitvector;
+use crate::int_vector::IntVector;
+use crate::signed_int_vector::SignedIntVector;
+
+#[cfg(test)]
+mod tests;
+
+// Reading and writing of the serialized sdsl-lite layouts. sdsl-lite writes `int_vector<w>` as
+// the size of the vector in bits (u64), the width of the values (u8, only when `w=0`) and
+// `ceil(size/64)` data words. `bit_vector` is `int_vector<1>`. All integers are little-endian.
+
+/// Writes the bitvector in the sdsl-lite `bit_vector` format.
+///
+/// ```
+/// use halko_rust::bitvectors::Bitvector;
+/// use halko_rust::sdsl_io;
+///
+/// let bv = Bitvector::build(&[1,0,1,1]);
+/// let mut bytes = Vec::new();
+/// sdsl_io::write_bit_vector(&bv, &mut bytes).unwrap();
+///
+/// assert_eq!(bytes, vec![4,0,0,0,0,0,0,0, 13,0,0,0,0,0,0,0]);
+/// ```
+pub fn write_bit_vector<W: Write>(bv: &Bitvector, w: &mut W) -> io::Result<()> {
+    let n = bv.len();
+    write_u64(w, n as u64)?;
+    write_words(w, &bv.get_data()[..n.div_ceil(64)], n)
+}
+
+/// Reads bitvector written in the sdsl-lite `bit_vector` format.
+///
+/// ```
+/// use halko_rust::sdsl_io;
+///
+/// let bytes: Vec<u8> = vec![4,0,0,0,0,0,0,0, 13,0,0,0,0,0,0,0];
+/// let bv = sdsl_io::read_bit_vector(&mut bytes.as_slice()).unwrap();
+///
+/// assert_eq!(bv.len(), 4);
+/// assert_eq!(bv.get(0), 1);
+/// assert_eq!(bv.get(1), 0);
+/// ```
+pub fn read_bit_vector<R: Read>(r: &mut R) -> io::Result<Bitvector> {
+    let n = read_size(r)?;
+    let mut data = read_words(r, n)?;
+    // Bitvector keeps one extra word when the length is a multiple of 64
+    data.resize(n/64+1, 0);
+    Ok(Bitvector::from_raw_parts(data, n))
+}
+
+/// Writes the vector in the sdsl-lite `int_vector<0>` format, where the width of the values is
+/// stored in the header.
+///
+/// ```
+/// use halko_rust::int_vector::IntVector;
+/// use halko_rust::sdsl_io;
+///
+/// let iv = IntVector::from_slice(&[1,0,15]);
+///
+/// let mut bytes = Vec::new();
+/// sdsl_io::write_int_vector(&iv, &mut bytes).unwrap();
+///
+/// assert_eq!(bytes, vec![12,0,0,0,0,0,0,0, 4, 1,15,0,0,0,0,0,0]);
+/// ```
+pub fn write_int_vector<W: Write>(iv: &IntVector, w: &mut W) -> io::Result<()> {
+    let bits = iv.len()*iv.get_l();
+    write_u64(w, bits as u64)?;
+    w.write_all(&[iv.get_l() as u8])?;
+    write_words(w, iv.get_data(), bits)
+}
+
+/// Reads vector written in the sdsl-lite `int_vector<0>` format.
+///
+/// ```
+/// use halko_rust::sdsl_io;
+///
+/// let bytes: Vec<u8> = vec![12,0,0,0,0,0,0,0, 4, 1,15,0,0,0,0,0,0];
+/// let iv = sdsl_io::read_int_vector(&mut bytes.as_slice()).unwrap();
+///
+/// assert_eq!(iv.get_l(), 4);
+/// assert_eq!(iv.len(), 3);
+/// assert_eq!(iv.get(2), 15);
+/// ```
+pub fn read_int_vector<R: Read>(r: &mut R) -> io::Result<IntVector> {
+    let bits = read_size(r)?;
+    let mut width = [0_u8; 1];
+    r.read_exact(&mut width)?;
+    read_int_vector_data(r, bits, width[0] as usize)
+}
+
+/// Writes the vector in the sdsl-lite `int_vector<w>` format, where `w` is fixed at compile time
+/// in C++ and is not stored. Function panics if the width of the vector is not `width`.
+///
+/// ```
+/// use halko_rust::int_vector::IntVector;
+/// use halko_rust::sdsl_io;
+///
+/// let iv = IntVector::from_slice(&[0,200]);
+///
+/// let mut bytes = Vec::new();
+/// sdsl_io::write_int_vector_fixed(&iv, 8, &mut bytes).unwrap();
+/// let iv2 = sdsl_io::read_int_vector_fixed(&mut bytes.as_slice(), 8).unwrap();
+///
+/// assert_eq!(bytes.len(), 16);
+/// assert_eq!(iv, iv2);
+/// ```
+pub fn write_int_vector_fixed<W: Write>(iv: &IntVector, width: usize, w: &mut W) -> io::Result<()> {
+    if iv.get_l() != width {
+        panic!("[sdsl_io::write_int_vector_fixed], Width of the vector does not match, width:{}, \
+               vector width:{}", width, iv.get_l());
+    }
+    let bits = iv.len()*width;
+    write_u64(w, bits as u64)?;
+    write_words(w, iv.get_data(), bits)
+}
+
+/// Reads vector written in the sdsl-lite `int_vector<w>` format with fixed width `width`.
+pub fn read_int_vector_fixed<R: Read>(r: &mut R, width: usize) -> io::Result<IntVector> {
+    let bits = read_size(r)?;
+    read_int_vector_data(r, bits, width)
+}
+
+/// Writes the zigzag encoded values of the vector in the sdsl-lite `int_vector<0>` format.
+///
+/// ```
+/// use halko_rust::signed_int_vector::SignedIntVector;
+/// use halko_rust::sdsl_io;
+///
+/// let siv = SignedIntVector::build(&[-3, 0, 2]);
+///
+/// let mut bytes = Vec::new();
+/// sdsl_io::write_signed_int_vector(&siv, &mut bytes).unwrap();
+/// let siv2 = sdsl_io::read_signed_int_vector(&mut bytes.as_slice()).unwrap();
+///
+/// assert_eq!(siv, siv2);
+/// ```
+pub fn write_signed_int_vector<W: Write>(siv: &SignedIntVector, w: &mut W) -> io::Result<()> {
+    write_int_vector(siv.get_int_vector(), w)
+}
+
+/// Reads vector of zigzag encoded values written in the sdsl-lite `int_vector<0>` format.
+pub fn read_signed_int_vector<R: Read>(r: &mut R) -> io::Result<SignedIntVector> {
+    Ok(SignedIntVector::from_int_vector(read_int_vector(r)?))
+}
+
+fn read_int_vector_data<R: Read>(r: &mut R, bits: usize, width: usize) -> io::Result<IntVector> {
+    if width == 0 || width > 64 {
+        return Err(invalid_data(format!("invalid width of int_vector, width:{}", width)));
+    }
+    if !bits.is_multiple_of(width) {
+        return Err(invalid_data(format!("size of int_vector is not a multiple of the width, \
+                                         size:{}, width:{}", bits, width)));
+    }
+    let data = read_words(r, bits)?;
+    Ok(IntVector::from_raw_parts(data, bits/width, width))
+}
+
+fn write_u64<W: Write>(w: &mut W, x: u64) -> io::Result<()> {
+    w.write_all(&x.to_le_bytes())
+}
+
+// Writes the words covering the first `bits` bits
+fn write_words<W: Write>(w: &mut W, data: &[u64], bits: usize) -> io::Result<()> {
+    let words = bits.div_ceil(64);
+    let mut buf = Vec::with_capacity(8*words);
+    for x in data[..words].iter() {
+        buf.extend_from_slice(&x.to_le_bytes());
+    }
+    w.write_all(&buf)
+}
+
+fn read_size<R: Read>(r: &mut R) -> io::Result<usize> {
+    let mut buf = [0_u8; 8];
+    r.read_exact(&mut buf)?;
+    usize::try_from(u64::from_le_bytes(buf))
+        .map_err(|_| invalid_data("size of the vector does not fit into usize".to_string()))
+}
+
+// Reads the words covering the first `bits` bits and clears the padding bits after them. The
+// buffer grows with the data that is actually read, so that a corrupt size in the header does
+// not allocate the whole size.
+fn read_words<R: Read>(r: &mut R, bits: usize) -> io::Result<Vec<u64>> {
+    let len = bits.div_ceil(64).checked_mul(8)
+        .ok_or_else(|| invalid_data(format!("size of the vector is too large, size:{}", bits)))?;
+    let mut buf = Vec::new();
+    r.take(len as u64).read_to_end(&mut buf)?;
+    if buf.len() < len {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof,
+                                  format!("vector data ended after {} of {} bytes", buf.len(), len)));
+    }
+    let mut data: Vec<u64> = buf.chunks_exact(8).map(|c| u64::from_le_bytes(c.try_into().unwrap())).collect();
+    if !bits.is_multiple_of(64) {
+        data[bits/64] &= u64::MAX >> (64 - bits%64);
+    }
+    Ok(data)
+}
+
+fn invalid_data(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
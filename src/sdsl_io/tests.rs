@@ -0,0 +1,189 @@
+use std::io::ErrorKind;
+use crate::sdsl_io::{read_bit_vector, write_bit_vector, read_int_vector, write_int_vector,
+                     read_int_vector_fixed, write_int_vector_fixed, read_signed_int_vector,
+                     write_signed_int_vector};
+use crate::bitvectors::{Bitvector, Bit};
+use crate::int_vector::IntVector;
+use crate::signed_int_vector::SignedIntVector;
+
+// Fixtures were written with `write_*` of this module and guard the layout against changes.
+// They are not generated by sdsl-lite, so they do not show compatibility with it.
+const BIT_VECTOR: &[u8] = include_bytes!("fixtures/bit_vector.sdsl");
+const INT_VECTOR_0: &[u8] = include_bytes!("fixtures/int_vector_0.sdsl");
+const INT_VECTOR_7: &[u8] = include_bytes!("fixtures/int_vector_7.sdsl");
+
+fn fixture_bits() -> Vec<u32> {
+    (0..70).map(|i| if i%5 == 0 || i%5 == 2 {1} else {0}).collect()
+}
+
+fn fixture_values0() -> Vec<u64> {
+    vec![3,1,4,1,5,9,2,6,5,3,5]
+}
+
+fn fixture_values7() -> Vec<u64> {
+    (0..20).map(|i| (i*37)%128).collect()
+}
+
+// fn: read_bit_vector
+#[test]
+fn read_bit_vector_fixture() {
+    let bv = read_bit_vector(&mut &BIT_VECTOR[..]).unwrap();
+    let bits = fixture_bits();
+
+    assert_eq!(bv.len(), bits.len());
+    for (i, b) in bits.iter().enumerate() {
+        assert_eq!(bv.get(i), *b);
+    }
+}
+
+// fn: write_bit_vector
+#[test]
+fn write_bit_vector_fixture() {
+    let bv = Bitvector::build(&fixture_bits());
+    let mut bytes = Vec::new();
+    write_bit_vector(&bv, &mut bytes).unwrap();
+
+    assert_eq!(bytes, BIT_VECTOR);
+}
+
+// fn: write_bit_vector
+#[test]
+fn write_bit_vector_multiple_of_64() {
+    let mut bv = Bitvector::build_empty(128);
+    bv.set(127, Bit::ONE);
+    let mut bytes = Vec::new();
+    write_bit_vector(&bv, &mut bytes).unwrap();
+    assert_eq!(bytes.len(), 8 + 2*8);
+
+    let bv2 = read_bit_vector(&mut bytes.as_slice()).unwrap();
+    assert_eq!(bv2.len(), 128);
+    assert_eq!(bv2.get(127), 1);
+    assert_eq!(bv2.rank1(127), 1);
+}
+
+// fn: read_int_vector
+#[test]
+fn read_int_vector_fixture() {
+    let iv = read_int_vector(&mut &INT_VECTOR_0[..]).unwrap();
+    assert_eq!(iv, IntVector::from_slice(&fixture_values0()));
+}
+
+// fn: write_int_vector
+#[test]
+fn write_int_vector_fixture() {
+    let mut bytes = Vec::new();
+    write_int_vector(&IntVector::from_slice(&fixture_values0()), &mut bytes).unwrap();
+    assert_eq!(bytes, INT_VECTOR_0);
+}
+
+// fn: read_int_vector_fixed
+#[test]
+fn read_int_vector_fixed_fixture() {
+    let iv = read_int_vector_fixed(&mut &INT_VECTOR_7[..], 7).unwrap();
+    assert_eq!(iv, IntVector::from_slice(&fixture_values7()));
+}
+
+// fn: write_int_vector_fixed
+#[test]
+fn write_int_vector_fixed_fixture() {
+    let mut bytes = Vec::new();
+    write_int_vector_fixed(&IntVector::from_slice(&fixture_values7()), 7, &mut bytes).unwrap();
+    assert_eq!(bytes, INT_VECTOR_7);
+}
+
+// fn: write_int_vector_fixed
+#[test]
+#[should_panic]
+fn write_int_vector_fixed_wrong_width() {
+    let mut bytes = Vec::new();
+    write_int_vector_fixed(&IntVector::new(3, 5), 7, &mut bytes).unwrap();
+}
+
+// fn: write_int_vector
+#[test]
+fn int_vector_round_trip_all_widths() {
+    for l in 1..=64_usize {
+        let mut values: Vec<u64> = (0..100_u64).map(|i| i.wrapping_mul(0x9e3779b97f4a7c15) >> (64-l)).collect();
+        values[0] = u64::MAX >> (64-l);
+        let iv = IntVector::from_slice(&values);
+        assert_eq!(iv.get_l(), l);
+        let mut bytes = Vec::new();
+        write_int_vector(&iv, &mut bytes).unwrap();
+
+        assert_eq!(bytes.len(), 9 + 8*(100*l).div_ceil(64));
+        assert_eq!(read_int_vector(&mut bytes.as_slice()).unwrap(), iv);
+    }
+}
+
+// fn: write_signed_int_vector, read_signed_int_vector
+#[test]
+fn signed_int_vector_round_trip() {
+    for v in [vec![], vec![0], vec![-5, 3, 0, -1, 7], vec![i64::MIN, -1, 0, i64::MAX]] {
+        let siv = SignedIntVector::build(&v);
+        let mut bytes = Vec::new();
+        write_signed_int_vector(&siv, &mut bytes).unwrap();
+        let siv2 = read_signed_int_vector(&mut bytes.as_slice()).unwrap();
+
+        assert_eq!(siv2, siv);
+        assert_eq!(siv2.width(), siv.width());
+        assert_eq!(siv2.to_vec(), v);
+    }
+}
+
+// fn: read_int_vector
+#[test]
+fn read_int_vector_invalid_data() {
+    // width 0
+    let bytes: Vec<u8> = vec![0,0,0,0,0,0,0,0, 0];
+    assert_eq!(read_int_vector(&mut bytes.as_slice()).unwrap_err().kind(), ErrorKind::InvalidData);
+
+    // size not multiple of the width
+    let bytes: Vec<u8> = vec![5,0,0,0,0,0,0,0, 4, 0,0,0,0,0,0,0,0];
+    assert_eq!(read_int_vector(&mut bytes.as_slice()).unwrap_err().kind(), ErrorKind::InvalidData);
+
+    // truncated data
+    assert_eq!(read_int_vector(&mut &INT_VECTOR_0[..12]).unwrap_err().kind(), ErrorKind::UnexpectedEof);
+}
+
+// fn: read_bit_vector, read_int_vector
+#[test]
+fn read_truncated_data() {
+    for len in [0, 4, 8, 9, 15] {
+        assert_eq!(read_bit_vector(&mut &BIT_VECTOR[..len]).unwrap_err().kind(), ErrorKind::UnexpectedEof);
+    }
+    let len = INT_VECTOR_7.len() - 1;
+    assert_eq!(read_int_vector_fixed(&mut &INT_VECTOR_7[..len], 7).unwrap_err().kind(), ErrorKind::UnexpectedEof);
+}
+
+// fn: read_bit_vector, read_int_vector
+#[test]
+fn read_huge_size_header() {
+    // size of 2^64-1 bits is not allocated before reading the data
+    let mut bytes = u64::MAX.to_le_bytes().to_vec();
+    bytes.extend_from_slice(&[0; 16]);
+    assert_eq!(read_bit_vector(&mut bytes.as_slice()).unwrap_err().kind(), ErrorKind::UnexpectedEof);
+
+    // size of 2^60 bits with only a few bytes of data
+    let mut bytes = (1_u64 << 60).to_le_bytes().to_vec();
+    bytes.push(8);
+    bytes.extend_from_slice(&[0; 16]);
+    assert_eq!(read_int_vector(&mut bytes.as_slice()).unwrap_err().kind(), ErrorKind::UnexpectedEof);
+}
+
+// fn: read_bit_vector, read_int_vector
+#[test]
+fn read_dirty_padding_bits() {
+    // bits 1,0,1,1 followed by 1s in the rest of the word
+    let mut bytes = 4_u64.to_le_bytes().to_vec();
+    bytes.extend_from_slice(&(u64::MAX - 2).to_le_bytes());
+    let bv = read_bit_vector(&mut bytes.as_slice()).unwrap();
+    assert_eq!(bv.get_data()[0], 0b1101);
+    assert_eq!(bv.count_ones_in_words(0..1), 3);
+
+    // values 1,0,15 of 4 bits followed by garbage
+    let mut bytes = 12_u64.to_le_bytes().to_vec();
+    bytes.push(4);
+    bytes.extend_from_slice(&(0xF01_u64 | (0xABCDE << 12)).to_le_bytes());
+    let iv = read_int_vector(&mut bytes.as_slice()).unwrap();
+    assert_eq!(iv, IntVector::from_slice(&[1,0,15]));
+}
@@ -0,0 +1,202 @@
+use std::fmt;
+use std::vec::Vec;
+use crate::int_vector::{IntVector, bit_length};
+use crate::structure::StructureNode;
+
+#[cfg(test)]
+mod tests;
+
+/// Maps signed integer into unsigned integer using zigzag encoding,
+/// `0 -> 0, -1 -> 1, 1 -> 2, -2 -> 3, ...`.
+///
+/// ```
+/// use halko_rust::signed_int_vector::zigzag_encode;
+///
+/// assert_eq!(zigzag_encode(0), 0);
+/// assert_eq!(zigzag_encode(-1), 1);
+/// assert_eq!(zigzag_encode(1), 2);
+/// assert_eq!(zigzag_encode(i64::MIN), u64::MAX);
+/// ```
+pub fn zigzag_encode(v: i64) -> u64 {
+    ((v << 1) ^ (v >> 63)) as u64
+}
+
+/// Inverse of the `zigzag_encode`.
+///
+/// ```
+/// use halko_rust::signed_int_vector::{zigzag_encode, zigzag_decode};
+///
+/// for v in [0, -1, 1, 1000, -1000, i64::MAX, i64::MIN] {
+///     assert_eq!(zigzag_decode(zigzag_encode(v)), v);
+/// }
+/// ```
+pub fn zigzag_decode(v: u64) -> i64 {
+    ((v >> 1) as i64) ^ -((v & 1) as i64)
+}
+
+/// Compact signed integer vector. Values are zigzag encoded and stored into `IntVector`,
+/// such that each value takes `l` bits.
+///
+/// ```
+/// use halko_rust::signed_int_vector::SignedIntVector;
+///
+/// let siv = SignedIntVector::build(&[-3, 0, 2, -1]);
+///
+/// assert_eq!(siv.len(), 4);
+/// assert_eq!(siv.get(0), -3);
+/// assert_eq!(siv.get(1), 0);
+/// assert_eq!(siv.get(2), 2);
+/// assert_eq!(siv.get(3), -1);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignedIntVector {
+    iv: IntVector,
+    l: usize, // size of max zigzag encoded value in bits
+}
+
+impl SignedIntVector {
+    /// Create new `SignedIntVector` of size `n` containing only 0s, where each value
+    /// is stored using `l` bits. Valid values are in range `[-2^(l-1), 2^(l-1)-1]`.
+    ///
+    /// ```
+    /// use halko_rust::signed_int_vector::SignedIntVector;
+    ///
+    /// let mut siv = SignedIntVector::new(3, 4);
+    /// siv.set(0, -8);
+    /// siv.set(2, 7);
+    ///
+    /// assert_eq!(siv.get(0), -8);
+    /// assert_eq!(siv.get(1), 0);
+    /// assert_eq!(siv.get(2), 7);
+    /// ```
+    pub fn new(n: usize, l: usize) -> SignedIntVector {
+        SignedIntVector {
+            iv: IntVector::new(n, l),
+            l,
+        }
+    }
+
+    /// Builds `SignedIntVector` from the input array. Length of the values in bits is
+    /// the smallest one, that fits both the minimum and maximum value of the array.
+    ///
+    /// ```
+    /// use halko_rust::signed_int_vector::SignedIntVector;
+    ///
+    /// let siv = SignedIntVector::build(&[-4, 1, 3]);
+    /// assert_eq!(siv.width(), 3);
+    /// assert_eq!(siv.to_vec(), vec![-4, 1, 3]);
+    /// ```
+    pub fn build(arr: &[i64]) -> SignedIntVector {
+        let min = arr.iter().min().copied().unwrap_or(0);
+        let max = arr.iter().max().copied().unwrap_or(0);
+        let zz_max = zigzag_encode(min).max(zigzag_encode(max));
+        let l = bit_length(zz_max);
+
+        let mut siv = SignedIntVector::new(arr.len(), l);
+        for (i, v) in arr.iter().enumerate() {
+            siv.set(i, *v);
+        }
+        siv
+    }
+
+    /// Builds `SignedIntVector` from the `IntVector` of zigzag encoded values, such as the one
+    /// returned by `get_int_vector`. The width of the values is the width of `iv`.
+    ///
+    /// ```
+    /// use halko_rust::int_vector::IntVector;
+    /// use halko_rust::signed_int_vector::SignedIntVector;
+    ///
+    /// let siv = SignedIntVector::from_int_vector(IntVector::from_slice(&[0, 1, 2]));
+    /// assert_eq!(siv.to_vec(), vec![0, -1, 1]);
+    /// ```
+    pub fn from_int_vector(iv: IntVector) -> SignedIntVector {
+        SignedIntVector {
+            l: iv.get_l(),
+            iv,
+        }
+    }
+
+    /// Set new value `new_val` into the `i`-th value in the `SignedIntVector`.
+    /// Function panics if `new_val` does not fit into `l` bits.
+    pub fn set(&mut self, i: usize, new_val: i64) {
+        let zz = zigzag_encode(new_val);
+        if self.l < 64 && zz >> self.l != 0 {
+            panic!("[SignedIntVector::set], Error setting new value into SignedIntVector, new value is too large. \
+                   new value: {}, valid range: [{},{}].",
+                   new_val, -(1_i64 << (self.l-1)), (1_i64 << (self.l-1))-1);
+        }
+        self.iv.set(i, zz);
+    }
+
+    /// Returns the `i`-th value in the `SignedIntVector`
+    pub fn get(&self, i: usize) -> i64 {
+        zigzag_decode(self.iv.get(i))
+    }
+
+    /// Returns the length of the SignedIntVector.
+    pub fn len(&self) -> usize {
+        self.iv.len()
+    }
+
+    /// Returns `true` if the SignedIntVector does not contain any values.
+    pub fn is_empty(&self) -> bool {
+        self.iv.len() == 0
+    }
+
+    /// Returns the size of the vector in bytes.
+    pub fn size_in_bytes(&self) -> usize {
+        self.iv.size_in_bytes() + 8
+    }
+
+    /// Returns the space breakdown of the vector.
+    pub fn structure_tree(&self) -> StructureNode {
+        StructureNode::new("SignedIntVector", self.size_in_bytes())
+            .with_field("l", self.l as u64)
+            .with_child(self.iv.structure_tree().with_name("iv"))
+    }
+
+    /// Returns the length of each value in bits.
+    pub fn width(&self) -> usize {
+        self.l
+    }
+
+    /// Returns iterator over the values of the SignedIntVector.
+    ///
+    /// ```
+    /// use halko_rust::signed_int_vector::SignedIntVector;
+    ///
+    /// let siv = SignedIntVector::build(&[5, -5, 0]);
+    /// let sum: i64 = siv.iter().sum();
+    /// assert_eq!(sum, 0);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = i64> + '_ {
+        (0..self.len()).map(move |i| self.get(i))
+    }
+
+    /// Decodes all values of the SignedIntVector into `Vec`.
+    pub fn to_vec(&self) -> Vec<i64> {
+        self.iter().collect()
+    }
+
+    /// Returns reference to the underlying `IntVector` of zigzag encoded values.
+    pub fn get_int_vector(&self) -> &IntVector {
+        &self.iv
+    }
+
+    /// Returns reference to the raw data of SignedIntVector.
+    pub fn get_data(&self) -> &Vec<u64> {
+        self.iv.get_data()
+    }
+}
+
+impl fmt::Display for SignedIntVector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut res = "[".to_string();
+        for v in self.iter() {
+            res.push_str(v.to_string().as_str());
+            res.push(',');
+        }
+        res.push(']');
+        write!(f, "{}", res)
+    }
+}
@@ -0,0 +1,112 @@
+use std::vec::Vec;
+use rand::{Rng, thread_rng};
+
+use crate::signed_int_vector::{SignedIntVector, zigzag_encode, zigzag_decode};
+
+// fn: zigzag_encode, zigzag_decode
+#[test]
+fn zigzag_small_values() {
+    let vals: Vec<i64> = vec![0, -1, 1, -2, 2, -3, 3];
+    for (i, v) in vals.iter().enumerate() {
+        assert_eq!(zigzag_encode(*v), i as u64);
+        assert_eq!(zigzag_decode(i as u64), *v);
+    }
+}
+
+// fn: zigzag_encode, zigzag_decode
+#[test]
+fn zigzag_extreme_values() {
+    assert_eq!(zigzag_encode(i64::MAX), u64::MAX-1);
+    assert_eq!(zigzag_encode(i64::MIN), u64::MAX);
+    assert_eq!(zigzag_decode(u64::MAX-1), i64::MAX);
+    assert_eq!(zigzag_decode(u64::MAX), i64::MIN);
+}
+
+// fn: new
+#[test]
+fn new_all_zeros() {
+    for l in 1..=64 {
+        let siv = SignedIntVector::new(10, l);
+        assert_eq!(siv.len(), 10);
+        assert_eq!(siv.width(), l);
+        for i in 0..10 { assert_eq!(siv.get(i), 0); }
+    }
+}
+
+// fn: build
+#[test]
+fn build_empty() {
+    let siv = SignedIntVector::build(&[]);
+    assert!(siv.is_empty());
+    assert_eq!(siv.width(), 1);
+}
+
+// fn: build
+#[test]
+fn build_minimal_width() {
+    assert_eq!(SignedIntVector::build(&[0, 0]).width(), 1);
+    assert_eq!(SignedIntVector::build(&[-1, 0]).width(), 1);
+    assert_eq!(SignedIntVector::build(&[1]).width(), 2);
+    assert_eq!(SignedIntVector::build(&[-2, 1]).width(), 2);
+    assert_eq!(SignedIntVector::build(&[-128, 127]).width(), 8);
+    assert_eq!(SignedIntVector::build(&[128]).width(), 9);
+    assert_eq!(SignedIntVector::build(&[i64::MIN, i64::MAX]).width(), 64);
+}
+
+// fn: build, get
+#[test]
+fn build_random() {
+    let mut rng = thread_rng();
+    for _ in 0..20 {
+        let n = rng.gen_range(1..500);
+        let bound = 1_i64 << rng.gen_range(0..62);
+        let v: Vec<i64> = (0..n).map(|_| rng.gen_range(-bound..=bound)).collect();
+        let siv = SignedIntVector::build(&v);
+        for (i, x) in v.iter().enumerate() {
+            assert_eq!(siv.get(i), *x);
+        }
+        assert_eq!(siv.to_vec(), v);
+    }
+}
+
+// fn: set
+#[test]
+fn set_overwrite_values() {
+    let mut siv = SignedIntVector::new(5, 5);
+    for i in 0..5 { siv.set(i, -16); }
+    siv.set(2, 15);
+    siv.set(3, 0);
+    assert_eq!(siv.to_vec(), vec![-16, -16, 15, 0, -16]);
+}
+
+// fn: set
+#[test]
+#[should_panic]
+fn set_too_large_value() {
+    let mut siv = SignedIntVector::new(5, 5);
+    siv.set(0, 16);
+}
+
+// fn: set
+#[test]
+#[should_panic]
+fn set_too_small_value() {
+    let mut siv = SignedIntVector::new(5, 5);
+    siv.set(0, -17);
+}
+
+// fn: iter
+#[test]
+fn iter_matches_get() {
+    let v: Vec<i64> = vec![3, -7, 0, 12, -1];
+    let siv = SignedIntVector::build(&v);
+    let res: Vec<i64> = siv.iter().collect();
+    assert_eq!(res, v);
+}
+
+// fn: Display
+#[test]
+fn print_small() {
+    let siv = SignedIntVector::build(&[-1, 2, 0]);
+    assert_eq!(format!("{siv}"), "[-1,2,0,]");
+}
@@ -0,0 +1,148 @@
+use std::fmt;
+use std::vec::Vec;
+
+#[cfg(test)]
+mod tests;
+
+/// Nested breakdown of the space used by a data structure, similar to sdsl-lite's
+/// `write_structure`. Each node has a name, the type of the structure, its size in bytes,
+/// numeric fields such as widths and lengths, and the nodes of its components.
+///
+/// ```
+/// use halko_rust::int_vector::IntVector;
+///
+/// let iv = IntVector::new(100, 5);
+/// let tree = iv.structure_tree();
+///
+/// assert_eq!(tree.get_size_in_bytes(), iv.size_in_bytes());
+/// assert_eq!(tree.to_text(), "IntVector: 80 bytes, l=5, n=100\n");
+/// assert_eq!(tree.to_json(),
+///            r#"{"name":"IntVector","type":"IntVector","size_in_bytes":80,"l":5,"n":100,"children":[]}"#);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructureNode {
+    name: String,
+    type_name: String,
+    size_in_bytes: usize,
+    fields: Vec<(String, u64)>,
+    children: Vec<StructureNode>,
+}
+
+impl StructureNode {
+    /// Creates node of structure `type_name` using `size_in_bytes` bytes.
+    /// The name of the node is the type name, until it is renamed with `with_name`.
+    pub fn new(type_name: &str, size_in_bytes: usize) -> StructureNode {
+        StructureNode {
+            name: type_name.to_string(),
+            type_name: type_name.to_string(),
+            size_in_bytes,
+            fields: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    /// Sets the name of the node, usually the name of the member in the parent structure.
+    pub fn with_name(mut self, name: &str) -> StructureNode {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Adds numeric field, such as length or width, into the node.
+    pub fn with_field(mut self, key: &str, value: u64) -> StructureNode {
+        self.fields.push((key.to_string(), value));
+        self
+    }
+
+    /// Adds component node into the node.
+    pub fn with_child(mut self, child: StructureNode) -> StructureNode {
+        self.children.push(child);
+        self
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    pub fn get_size_in_bytes(&self) -> usize {
+        self.size_in_bytes
+    }
+
+    pub fn get_fields(&self) -> &Vec<(String, u64)> {
+        &self.fields
+    }
+
+    pub fn get_children(&self) -> &Vec<StructureNode> {
+        &self.children
+    }
+
+    /// Renders the tree as indented text, one node per line.
+    pub fn to_text(&self) -> String {
+        let mut res = String::new();
+        self.write_text(&mut res, 0);
+        res
+    }
+
+    fn write_text(&self, res: &mut String, depth: usize) {
+        res.push_str(&"  ".repeat(depth));
+        res.push_str(&self.name);
+        if self.name != self.type_name {
+            res.push_str(&format!(" ({})", self.type_name));
+        }
+        res.push_str(&format!(": {} bytes", self.size_in_bytes));
+        for (key, value) in self.fields.iter() {
+            res.push_str(&format!(", {}={}", key, value));
+        }
+        res.push('\n');
+        for child in self.children.iter() {
+            child.write_text(res, depth+1);
+        }
+    }
+
+    /// Renders the tree as JSON object. Fields are written as members of the object, and
+    /// components into the `children` array.
+    pub fn to_json(&self) -> String {
+        let mut res = String::new();
+        self.write_json(&mut res);
+        res
+    }
+
+    fn write_json(&self, res: &mut String) {
+        res.push_str(&format!("{{\"name\":{},\"type\":{},\"size_in_bytes\":{}",
+                              json_string(&self.name), json_string(&self.type_name), self.size_in_bytes));
+        for (key, value) in self.fields.iter() {
+            res.push_str(&format!(",{}:{}", json_string(key), value));
+        }
+        res.push_str(",\"children\":[");
+        for (i, child) in self.children.iter().enumerate() {
+            if i > 0 {
+                res.push(',');
+            }
+            child.write_json(res);
+        }
+        res.push_str("]}");
+    }
+}
+
+impl fmt::Display for StructureNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_text())
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut res = "\"".to_string();
+    for c in s.chars() {
+        match c {
+            '"' => res.push_str("\\\""),
+            '\\' => res.push_str("\\\\"),
+            c if (c as u32) < 0x20 => res.push_str(&format!("\\u{:04x}", c as u32)),
+            c => res.push(c),
+        }
+    }
+    res.push('"');
+    res
+}
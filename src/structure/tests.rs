@@ -0,0 +1,66 @@
+use crate::structure::StructureNode;
+use crate::bitvectors::Bitvector;
+use crate::int_vector::IntVector;
+use crate::rank_support::RankSupport;
+
+fn small_tree() -> StructureNode {
+    StructureNode::new("Parent", 30)
+        .with_field("n", 3)
+        .with_child(StructureNode::new("Child", 10).with_name("a"))
+        .with_child(StructureNode::new("Child", 20).with_name("b").with_field("l", 7))
+}
+
+// fn: to_text
+#[test]
+fn to_text_small_tree() {
+    assert_eq!(small_tree().to_text(),
+               "Parent: 30 bytes, n=3\n  a (Child): 10 bytes\n  b (Child): 20 bytes, l=7\n");
+}
+
+// fn: to_json
+#[test]
+fn to_json_small_tree() {
+    assert_eq!(small_tree().to_json(),
+               "{\"name\":\"Parent\",\"type\":\"Parent\",\"size_in_bytes\":30,\"n\":3,\"children\":[\
+               {\"name\":\"a\",\"type\":\"Child\",\"size_in_bytes\":10,\"children\":[]},\
+               {\"name\":\"b\",\"type\":\"Child\",\"size_in_bytes\":20,\"l\":7,\"children\":[]}]}");
+}
+
+// fn: to_json
+#[test]
+fn to_json_escapes_names() {
+    let node = StructureNode::new("T", 0).with_name("a\"b\\c\n");
+    assert_eq!(node.to_json(), "{\"name\":\"a\\\"b\\\\c\\u000a\",\"type\":\"T\",\"size_in_bytes\":0,\"children\":[]}");
+}
+
+// fn: size_in_bytes
+#[test]
+fn size_in_bytes_basic_structures() {
+    let bv = Bitvector::build_empty(1000);
+    assert_eq!(bv.size_in_bytes(), 8*bv.get_data().len() + 8);
+
+    let iv = IntVector::new(1000, 7);
+    assert_eq!(iv.size_in_bytes(), 8*iv.get_data().len() + 16);
+
+    let rs = RankSupport::new(Bitvector::build_empty(1000));
+    assert_eq!(rs.size_in_bytes(), rs.get_bv().size_in_bytes() + rs.get_block_level1().size_in_bytes()
+                                   + rs.get_block_level2().size_in_bytes() + 16);
+}
+
+// fn: structure_tree
+#[test]
+fn structure_tree_rank_support() {
+    let rs = RankSupport::new(Bitvector::build_from_vec(&[u64::MAX; 4]));
+    let tree = rs.structure_tree();
+
+    assert_eq!(tree.get_size_in_bytes(), rs.size_in_bytes());
+    let names: Vec<&str> = tree.get_children().iter().map(|c| c.get_name()).collect();
+    assert_eq!(names, vec!["bv", "block_level1", "block_level2"]);
+
+    let level1 = &tree.get_children()[1];
+    assert_eq!(*level1.get_fields(), vec![("l".to_string(), 8), ("n".to_string(), 4)]);
+    assert_eq!(tree.to_text(), "RankSupport: 120 bytes, b1=64, b2=8\n  \
+                                bv (Bitvector): 40 bytes, n=256\n  \
+                                block_level1 (IntVector): 24 bytes, l=8, n=4\n  \
+                                block_level2 (IntVector): 40 bytes, l=6, n=32\n");
+}
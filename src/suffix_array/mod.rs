@@ -0,0 +1,203 @@
+use std::vec::Vec;
+use crate::int_vector::{IntVector, bit_length};
+
+#[cfg(test)]
+mod tests;
+
+const EMPTY: usize = usize::MAX;
+
+/// Builds the suffix array of `text` in linear time with SA-IS. The `i`-th value of the result
+/// is the starting position of the `i`-th smallest suffix of `text`. Values are stored with
+/// `ceil(log2(n))` bits, and at least 1 bit.
+///
+/// ```
+/// use halko_rust::suffix_array::suffix_array;
+///
+/// let sa = suffix_array(b"banana");
+///
+/// // a, ana, anana, banana, na, nana
+/// let expected = [5,3,1,0,4,2];
+/// for (i, x) in expected.iter().enumerate() {
+///     assert_eq!(sa.get(i), *x);
+/// }
+/// assert_eq!(sa.get_l(), 3);
+/// ```
+pub fn suffix_array(text: &[u8]) -> IntVector {
+    let s: Vec<usize> = text.iter().map(|c| *c as usize + 1).collect();
+    build(s, 257)
+}
+
+/// Builds the suffix array of the integer sequence `text` with SA-IS. The values are first
+/// replaced with their ranks among the distinct values, so any values up to `u64::MAX` are
+/// supported and the alphabet has at most `n` symbols, taking `O(n log n)` time in total.
+///
+/// ```
+/// use halko_rust::int_vector::IntVector;
+/// use halko_rust::suffix_array::suffix_array_int;
+///
+/// let a = [2,1,2,1,0];
+/// let text = IntVector::from_slice(&a);
+/// let sa = suffix_array_int(&text);
+///
+/// let expected = [4,3,1,2,0];
+/// for (i, x) in expected.iter().enumerate() {
+///     assert_eq!(sa.get(i), *x);
+/// }
+/// ```
+pub fn suffix_array_int(text: &IntVector) -> IntVector {
+    let values = text.to_vec();
+    let mut alphabet = values.clone();
+    alphabet.sort_unstable();
+    alphabet.dedup();
+    // rank of each value in range [1,sigma), 0 is reserved for the sentinel
+    let s: Vec<usize> = values.iter().map(|x| alphabet.binary_search(x).unwrap() + 1).collect();
+    build(s, alphabet.len() + 1)
+}
+
+/// Returns the inverse suffix array, where `isa[sa[i]] = i`. Values use the same width as `sa`.
+///
+/// ```
+/// use halko_rust::suffix_array::{suffix_array, inverse_suffix_array};
+///
+/// let sa = suffix_array(b"banana");
+/// let isa = inverse_suffix_array(&sa);
+///
+/// for i in 0..sa.len() {
+///     assert_eq!(isa.get(sa.get(i) as usize), i as u64);
+/// }
+/// ```
+pub fn inverse_suffix_array(sa: &IntVector) -> IntVector {
+    let n = sa.len();
+    let mut isa = IntVector::new(n, sa.get_l());
+    for i in 0..n {
+        isa.set(sa.get(i) as usize, i as u64);
+    }
+    isa
+}
+
+// Appends sentinel 0 into `s`, whose values are in range [1,sigma), and packs the suffix array
+// without the sentinel suffix.
+fn build(mut s: Vec<usize>, sigma: usize) -> IntVector {
+    let n = s.len();
+    s.push(0);
+    let sa = sais(&s, sigma);
+
+    let l = bit_length((n as u64).saturating_sub(1));
+    let mut iv = IntVector::new(n, l);
+    for (i, x) in sa[1..].iter().enumerate() {
+        iv.set(i, *x as u64);
+    }
+    iv
+}
+
+// SA-IS for `s` with values in range [0,k), where the last value is the only 0.
+fn sais(s: &[usize], k: usize) -> Vec<usize> {
+    let n = s.len();
+    if n == 1 {
+        return vec![0];
+    }
+
+    // t[i] is true if the i-th suffix is S-type
+    let mut t = vec![false; n];
+    t[n-1] = true;
+    for i in (0..n-1).rev() {
+        t[i] = s[i] < s[i+1] || (s[i] == s[i+1] && t[i+1]);
+    }
+    let is_lms = |i: usize| i > 0 && t[i] && !t[i-1];
+
+    let mut counts = vec![0; k];
+    for c in s.iter() {
+        counts[*c] += 1;
+    }
+
+    // sort LMS substrings by induced sorting from the LMS positions in text order
+    let lms: Vec<usize> = (1..n).filter(|i| is_lms(*i)).collect();
+    let sa = induce(s, &t, &counts, &lms);
+
+    // name the LMS substrings in sorted order
+    let sorted_lms: Vec<usize> = sa.iter().copied().filter(|i| is_lms(*i)).collect();
+    let mut names = vec![EMPTY; n];
+    let mut name = 0;
+    names[sorted_lms[0]] = 0;
+    for w in sorted_lms.windows(2) {
+        if !lms_substrings_equal(s, &t, w[0], w[1]) {
+            name += 1;
+        }
+        names[w[1]] = name;
+    }
+
+    // sort the LMS suffixes, recursively if the names are not unique
+    let reduced: Vec<usize> = lms.iter().map(|i| names[*i]).collect();
+    let sorted_lms = if name+1 == lms.len() {
+        let mut order = vec![0; lms.len()];
+        for (j, x) in reduced.iter().enumerate() {
+            order[*x] = lms[j];
+        }
+        order
+    } else {
+        sais(&reduced, name+1).iter().map(|j| lms[*j]).collect()
+    };
+
+    induce(s, &t, &counts, &sorted_lms)
+}
+
+// Places the LMS suffixes `lms` at the ends of their buckets, keeping their order, and induces
+// the order of the L-type and the S-type suffixes.
+fn induce(s: &[usize], t: &[bool], counts: &[usize], lms: &[usize]) -> Vec<usize> {
+    let n = s.len();
+    let mut sa = vec![EMPTY; n];
+
+    let mut ends = bucket_ends(counts);
+    for j in lms.iter().rev() {
+        ends[s[*j]] -= 1;
+        sa[ends[s[*j]]] = *j;
+    }
+
+    let mut starts = bucket_starts(counts);
+    for i in 0..n {
+        let j = sa[i];
+        if j != EMPTY && j > 0 && !t[j-1] {
+            sa[starts[s[j-1]]] = j-1;
+            starts[s[j-1]] += 1;
+        }
+    }
+
+    let mut ends = bucket_ends(counts);
+    for i in (0..n).rev() {
+        let j = sa[i];
+        if j != EMPTY && j > 0 && t[j-1] {
+            ends[s[j-1]] -= 1;
+            sa[ends[s[j-1]]] = j-1;
+        }
+    }
+    sa
+}
+
+fn bucket_starts(counts: &[usize]) -> Vec<usize> {
+    let mut sum = 0;
+    counts.iter().map(|c| {sum += c; sum - c}).collect()
+}
+
+fn bucket_ends(counts: &[usize]) -> Vec<usize> {
+    let mut sum = 0;
+    counts.iter().map(|c| {sum += c; sum}).collect()
+}
+
+// Compares the LMS substrings starting at positions `a` and `b`, including the types.
+fn lms_substrings_equal(s: &[usize], t: &[bool], a: usize, b: usize) -> bool {
+    let n = s.len();
+    if a == n-1 || b == n-1 {
+        return a == b;
+    }
+    let is_lms = |i: usize| i > 0 && t[i] && !t[i-1];
+    let mut d = 0;
+    loop {
+        if s[a+d] != s[b+d] || t[a+d] != t[b+d] {
+            return false;
+        }
+        if d > 0 && (is_lms(a+d) || is_lms(b+d)) {
+            return is_lms(a+d) && is_lms(b+d);
+        }
+        d += 1;
+    }
+}
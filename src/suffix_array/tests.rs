@@ -0,0 +1,92 @@
+use std::vec::Vec;
+use rand::{Rng, thread_rng};
+
+use crate::suffix_array::{suffix_array, suffix_array_int, inverse_suffix_array};
+use crate::int_vector::IntVector;
+
+fn naive_suffix_array<T: Ord>(text: &[T]) -> Vec<u64> {
+    let mut sa: Vec<usize> = (0..text.len()).collect();
+    sa.sort_by(|a, b| text[*a..].cmp(&text[*b..]));
+    sa.iter().map(|x| *x as u64).collect()
+}
+
+// fn: suffix_array
+#[test]
+fn sa_small_texts() {
+    for text in [&b""[..], b"a", b"aaaaaaaa", b"abababab", b"mississippi", b"abracadabra",
+                 b"zyxwvutsrqponm", b"\x00\x00\xff\x00\xff\xff"] {
+        assert_eq!(suffix_array(text).to_vec(), naive_suffix_array(text));
+    }
+}
+
+// fn: suffix_array
+#[test]
+fn sa_random_texts() {
+    let mut rng = thread_rng();
+    for sigma in [2, 4, 26, 256] {
+        for n in [2, 10, 100, 5000] {
+            let text: Vec<u8> = (0..n).map(|_| rng.gen_range(0..sigma) as u8).collect();
+            assert_eq!(suffix_array(&text).to_vec(), naive_suffix_array(&text));
+        }
+    }
+}
+
+// fn: suffix_array
+#[test]
+fn sa_repetitive_text() {
+    let text: Vec<u8> = b"abaababaabaab".iter().cycle().take(3000).copied().collect();
+    assert_eq!(suffix_array(&text).to_vec(), naive_suffix_array(&text));
+}
+
+// fn: suffix_array
+#[test]
+fn sa_minimal_width() {
+    assert_eq!(suffix_array(b"").get_l(), 1);
+    assert_eq!(suffix_array(b"a").get_l(), 1);
+    assert_eq!(suffix_array(b"ab").get_l(), 1);
+    assert_eq!(suffix_array(b"abc").get_l(), 2);
+    assert_eq!(suffix_array(&[0; 256]).get_l(), 8);
+    assert_eq!(suffix_array(&[0; 257]).get_l(), 9);
+}
+
+// fn: suffix_array_int
+#[test]
+fn sa_int_random_texts() {
+    let mut rng = thread_rng();
+    for sigma in [1, 3, 1000] {
+        let text: Vec<u64> = (0..2000).map(|_| rng.gen_range(0..sigma)).collect();
+        let iv = IntVector::from_slice(&text);
+        assert_eq!(suffix_array_int(&iv).to_vec(), naive_suffix_array(&text));
+    }
+}
+
+// fn: suffix_array_int
+#[test]
+fn sa_int_large_values() {
+    let mut rng = thread_rng();
+    // values near u64::MAX
+    let text: Vec<u64> = (0..2000).map(|_| u64::MAX - rng.gen_range(0..4)).collect();
+    let iv = IntVector::from_slice(&text);
+    assert_eq!(suffix_array_int(&iv).to_vec(), naive_suffix_array(&text));
+
+    // few sparse values of width 40
+    let symbols = [0, 1 << 39, (1 << 40) - 1, 123_456_789_012];
+    let text: Vec<u64> = (0..2000).map(|_| symbols[rng.gen_range(0..symbols.len())]).collect();
+    let iv = IntVector::from_slice(&text);
+    assert_eq!(iv.get_l(), 40);
+    assert_eq!(suffix_array_int(&iv).to_vec(), naive_suffix_array(&text));
+}
+
+// fn: inverse_suffix_array
+#[test]
+fn isa_random_text() {
+    let mut rng = thread_rng();
+    let text: Vec<u8> = (0..1000).map(|_| rng.gen_range(b'a'..=b'd')).collect();
+    let sa = suffix_array(&text);
+    let isa = inverse_suffix_array(&sa);
+
+    assert_eq!(isa.get_l(), sa.get_l());
+    for i in 0..text.len() {
+        assert_eq!(sa.get(isa.get(i) as usize), i as u64);
+    }
+}
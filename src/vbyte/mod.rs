@@ -0,0 +1,313 @@
+use std::vec::Vec;
+use crate::int_vector::{IntVector, bit_length};
+use crate::structure::StructureNode;
+
+#[cfg(test)]
+mod tests;
+
+/// Byte-aligned encodings supported by the `VByteVector`.
+///
+/// * `VByte` stores 7 bits of the value into each byte, starting from the lowest bits.
+///   The highest bit of the byte is 1 if the value continues into the next byte.
+/// * `StreamVByte` stores the lengths of 4 values into one control byte using 2 bits per value,
+///   and the values as little-endian bytes after the control bytes of the block.
+///   Length codes `0,1,2,3` mean 1, 2, 4 and 8 bytes, so any `u64` value can be stored.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VByteFormat {
+    VByte,
+    StreamVByte,
+}
+
+const STREAM_LENGTHS: [usize; 4] = [1, 2, 4, 8];
+
+/// Integer sequence encoded with byte-aligned codes. Values are split into blocks of
+/// `block_size` values, and the byte offset of each block is stored into `IntVector`,
+/// such that any block can be decoded without decoding the previous ones.
+///
+/// ```
+/// use halko_rust::vbyte::{VByteVector, VByteFormat};
+///
+/// let v: Vec<u64> = vec![1, 300, 0, 70_000, 5];
+/// let vb = VByteVector::build(&v, VByteFormat::VByte, 2);
+///
+/// assert_eq!(vb.len(), 5);
+/// assert_eq!(vb.num_blocks(), 3);
+/// for i in 0..v.len() { assert_eq!(vb.get(i), v[i]); }
+/// assert_eq!(vb.get_block(1), vec![0, 70_000]);
+/// ```
+pub struct VByteVector {
+    data: Vec<u8>,
+    block_offsets: IntVector,
+    format: VByteFormat,
+    block_size: usize,
+    n: usize,
+}
+
+impl VByteVector {
+    /// Builds `VByteVector` from the input array using blocks of `block_size` values.
+    /// Function panics if `block_size=0`.
+    pub fn build(arr: &[u64], format: VByteFormat, block_size: usize) -> VByteVector {
+        if block_size == 0 {
+            panic!("[VByteVector::build], Block size must be greater than 0 (zero).");
+        }
+
+        let mut data: Vec<u8> = Vec::new();
+        let mut offsets: Vec<u64> = Vec::new();
+        for block in arr.chunks(block_size) {
+            offsets.push(data.len() as u64);
+            match format {
+                VByteFormat::VByte => encode_vbyte_block(block, &mut data),
+                VByteFormat::StreamVByte => encode_stream_block(block, &mut data),
+            }
+        }
+
+        let block_offsets = IntVector::from_slice(&offsets);
+
+        VByteVector {
+            data,
+            block_offsets,
+            format,
+            block_size,
+            n: arr.len(),
+        }
+    }
+
+    /// Builds `VByteVector` from the `IntVector` using blocks of `block_size` values.
+    ///
+    /// ```
+    /// use halko_rust::int_vector::IntVector;
+    /// use halko_rust::vbyte::{VByteVector, VByteFormat};
+    ///
+    /// let iv = IntVector::from_slice(&[0, 1 << 31, 0]);
+    ///
+    /// let vb = VByteVector::from_int_vector(&iv, VByteFormat::StreamVByte, 128);
+    /// assert_eq!(vb.to_int_vector(), iv);
+    /// ```
+    pub fn from_int_vector(iv: &IntVector, format: VByteFormat, block_size: usize) -> VByteVector {
+        let v: Vec<u64> = iv.to_vec();
+        VByteVector::build(&v, format, block_size)
+    }
+
+    /// Decodes all values into `IntVector`, where each value is stored using the number of
+    /// bits of the largest value.
+    pub fn to_int_vector(&self) -> IntVector {
+        let v: Vec<u64> = self.iter().collect();
+        IntVector::from_slice(&v)
+    }
+
+    /// Returns the `i`-th value by decoding values of its block up to the `i`-th value.
+    pub fn get(&self, i: usize) -> u64 {
+        if i >= self.n {
+            panic!("[VByteVector::get], Index out of bounds, i:{}, VByteVector length:{}", i, self.n);
+        }
+
+        let b = i / self.block_size;
+        let k = i % self.block_size;
+        let mut pos = self.block_offsets.get(b) as usize;
+        match self.format {
+            VByteFormat::VByte => {
+                for _ in 0..k {
+                    pos = decode_vbyte(&self.data, pos).1;
+                }
+                decode_vbyte(&self.data, pos).0
+            },
+            VByteFormat::StreamVByte => {
+                // skip values using the lengths in control bytes
+                let m = self.block_len(b);
+                let mut data_pos = pos + m.div_ceil(4);
+                for j in 0..k {
+                    data_pos += stream_length(self.data[pos + j/4], j%4);
+                }
+                read_le(&self.data, data_pos, stream_length(self.data[pos + k/4], k%4))
+            },
+        }
+    }
+
+    /// Decodes all values of the `b`-th block.
+    pub fn get_block(&self, b: usize) -> Vec<u64> {
+        let mut res = Vec::with_capacity(self.block_size);
+        self.decode_block(b, &mut res);
+        res
+    }
+
+    /// Appends decoded values of the `b`-th block into `out`.
+    pub fn decode_block(&self, b: usize, out: &mut Vec<u64>) {
+        if b >= self.num_blocks() {
+            panic!("[VByteVector::decode_block], Block index out of bounds, b:{}, number of blocks:{}",
+                   b, self.num_blocks());
+        }
+
+        let m = self.block_len(b);
+        let mut pos = self.block_offsets.get(b) as usize;
+        match self.format {
+            VByteFormat::VByte => {
+                for _ in 0..m {
+                    let (x, next) = decode_vbyte(&self.data, pos);
+                    out.push(x);
+                    pos = next;
+                }
+            },
+            VByteFormat::StreamVByte => {
+                let mut data_pos = pos + m.div_ceil(4);
+                for j in 0..m {
+                    let len = stream_length(self.data[pos + j/4], j%4);
+                    out.push(read_le(&self.data, data_pos, len));
+                    data_pos += len;
+                }
+            },
+        }
+    }
+
+    /// Returns iterator, that decodes the values block by block.
+    ///
+    /// ```
+    /// use halko_rust::vbyte::{VByteVector, VByteFormat};
+    ///
+    /// let v: Vec<u64> = vec![9, 8, 7, 1 << 40];
+    /// let vb = VByteVector::build(&v, VByteFormat::StreamVByte, 3);
+    ///
+    /// assert_eq!(vb.iter().collect::<Vec<u64>>(), v);
+    /// ```
+    pub fn iter(&self) -> VByteVectorIter<'_> {
+        VByteVectorIter {
+            vb: self,
+            buffer: Vec::with_capacity(self.block_size),
+            b: 0,
+            k: 0,
+        }
+    }
+
+    /// Returns the length of the VByteVector.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    /// Returns `true` if the VByteVector does not contain any values.
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Returns the size of the vector in bytes, including the encoded bytes and the block offsets.
+    pub fn size_in_bytes(&self) -> usize {
+        self.data.len() + self.block_offsets.size_in_bytes() + 24
+    }
+
+    /// Returns the space breakdown of the vector.
+    pub fn structure_tree(&self) -> StructureNode {
+        StructureNode::new("VByteVector", self.size_in_bytes())
+            .with_field("block_size", self.block_size as u64)
+            .with_field("n", self.n as u64)
+            .with_child(StructureNode::new("Vec<u8>", self.data.len())
+                        .with_name("data")
+                        .with_field("n", self.data.len() as u64))
+            .with_child(self.block_offsets.structure_tree().with_name("block_offsets"))
+    }
+
+    /// Returns the number of blocks.
+    pub fn num_blocks(&self) -> usize {
+        self.block_offsets.len()
+    }
+
+    /// Returns the number of values in each block, the last block can be smaller.
+    pub fn get_block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /// Returns the encoding format of the values.
+    pub fn get_format(&self) -> VByteFormat {
+        self.format
+    }
+
+    /// Returns reference to the byte offsets of the blocks.
+    pub fn get_block_offsets(&self) -> &IntVector {
+        &self.block_offsets
+    }
+
+    /// Returns reference to the encoded bytes.
+    pub fn get_data(&self) -> &Vec<u8> {
+        &self.data
+    }
+
+    fn block_len(&self, b: usize) -> usize {
+        self.block_size.min(self.n - b*self.block_size)
+    }
+}
+
+/// Sequential iterator over the values of `VByteVector`.
+pub struct VByteVectorIter<'a> {
+    vb: &'a VByteVector,
+    buffer: Vec<u64>, // decoded values of the current block
+    b: usize, // next block to decode
+    k: usize, // next value in the buffer
+}
+
+impl Iterator for VByteVectorIter<'_> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        if self.k == self.buffer.len() {
+            if self.b >= self.vb.num_blocks() {
+                return None;
+            }
+            self.buffer.clear();
+            self.vb.decode_block(self.b, &mut self.buffer);
+            self.b += 1;
+            self.k = 0;
+        }
+        self.k += 1;
+        Some(self.buffer[self.k-1])
+    }
+}
+
+fn encode_vbyte_block(block: &[u64], data: &mut Vec<u8>) {
+    for x in block {
+        let mut x = *x;
+        while x >= 0x80 {
+            data.push((x & 0x7f) as u8 | 0x80);
+            x >>= 7;
+        }
+        data.push(x as u8);
+    }
+}
+
+/// Decodes VByte value starting from `data[pos]`.
+/// Returns tuple `(x,j)`, where `x` is the value and `j` the index of the next value.
+fn decode_vbyte(data: &[u8], pos: usize) -> (u64, usize) {
+    let mut x = 0;
+    let mut shift = 0;
+    let mut pos = pos;
+    loop {
+        let byte = data[pos];
+        x |= ((byte & 0x7f) as u64) << shift;
+        pos += 1;
+        if byte & 0x80 == 0 {
+            return (x, pos);
+        }
+        shift += 7;
+    }
+}
+
+fn encode_stream_block(block: &[u64], data: &mut Vec<u8>) {
+    let control_start = data.len();
+    data.resize(control_start + block.len().div_ceil(4), 0);
+    for (j, x) in block.iter().enumerate() {
+        let code = match bit_length(*x) {
+            0..=8 => 0,
+            9..=16 => 1,
+            17..=32 => 2,
+            _ => 3,
+        };
+        data[control_start + j/4] |= (code as u8) << (2*(j%4));
+        data.extend_from_slice(&x.to_le_bytes()[..STREAM_LENGTHS[code]]);
+    }
+}
+
+fn stream_length(control: u8, j: usize) -> usize {
+    STREAM_LENGTHS[((control >> (2*j)) & 3) as usize]
+}
+
+fn read_le(data: &[u8], pos: usize, len: usize) -> u64 {
+    let mut bytes = [0_u8; 8];
+    bytes[..len].copy_from_slice(&data[pos..pos+len]);
+    u64::from_le_bytes(bytes)
+}
@@ -0,0 +1,125 @@
+use std::vec::Vec;
+use rand::{Rng, thread_rng};
+
+use crate::vbyte::{VByteVector, VByteFormat};
+use crate::int_vector::IntVector;
+
+const FORMATS: [VByteFormat; 2] = [VByteFormat::VByte, VByteFormat::StreamVByte];
+
+// fn: build
+#[test]
+fn build_vbyte_bytes() {
+    let vb = VByteVector::build(&[0, 127, 128, 300], VByteFormat::VByte, 128);
+    assert_eq!(*vb.get_data(), vec![0x00, 0x7f, 0x80, 0x01, 0xac, 0x02]);
+}
+
+// fn: build
+#[test]
+fn build_stream_vbyte_bytes() {
+    let vb = VByteVector::build(&[1, 256, 1 << 16, 1 << 32, 2], VByteFormat::StreamVByte, 128);
+    let mut res: Vec<u8> = vec![0b11_10_01_00, 0b00];
+    res.extend_from_slice(&[1]);
+    res.extend_from_slice(&[0, 1]);
+    res.extend_from_slice(&[0, 0, 1, 0]);
+    res.extend_from_slice(&[0, 0, 0, 0, 1, 0, 0, 0]);
+    res.extend_from_slice(&[2]);
+    assert_eq!(*vb.get_data(), res);
+}
+
+// fn: build
+#[test]
+fn build_empty() {
+    for format in FORMATS {
+        let vb = VByteVector::build(&[], format, 4);
+        assert!(vb.is_empty());
+        assert_eq!(vb.num_blocks(), 0);
+        assert_eq!(vb.iter().count(), 0);
+    }
+}
+
+// fn: build
+#[test]
+#[should_panic]
+fn build_zero_block_size() {
+    let _vb = VByteVector::build(&[1], VByteFormat::VByte, 0);
+}
+
+// fn: build
+#[test]
+fn build_block_offsets() {
+    let v: Vec<u64> = vec![1, 1, 200, 200, 1];
+    let vb = VByteVector::build(&v, VByteFormat::VByte, 2);
+    let offsets: Vec<u64> = (0..vb.num_blocks()).map(|b| vb.get_block_offsets().get(b)).collect();
+    assert_eq!(offsets, vec![0, 2, 6]);
+}
+
+// fn: get
+#[test]
+fn get_random() {
+    let mut rng = thread_rng();
+    for format in FORMATS {
+        for _ in 0..5 {
+            let n = rng.gen_range(1..1000);
+            let v: Vec<u64> = (0..n).map(|_| {
+                let l = rng.gen_range(1..=64);
+                rng.gen::<u64>() >> (64-l)
+            }).collect();
+            let vb = VByteVector::build(&v, format, rng.gen_range(1..200));
+            for (i, x) in v.iter().enumerate() {
+                assert_eq!(vb.get(i), *x, "format:{:?}, i:{}", format, i);
+            }
+        }
+    }
+}
+
+// fn: get
+#[test]
+#[should_panic]
+fn get_index_out_of_bounds() {
+    let vb = VByteVector::build(&[1, 2, 3], VByteFormat::StreamVByte, 2);
+    vb.get(3);
+}
+
+// fn: get_block
+#[test]
+fn get_block_last_partial() {
+    let v: Vec<u64> = (0..10).map(|x| x*1000).collect();
+    for format in FORMATS {
+        let vb = VByteVector::build(&v, format, 4);
+        assert_eq!(vb.num_blocks(), 3);
+        assert_eq!(vb.get_block(0), v[0..4].to_vec());
+        assert_eq!(vb.get_block(2), v[8..10].to_vec());
+    }
+}
+
+// fn: get_block
+#[test]
+#[should_panic]
+fn get_block_out_of_bounds() {
+    let vb = VByteVector::build(&[1, 2, 3], VByteFormat::VByte, 2);
+    vb.get_block(2);
+}
+
+// fn: iter
+#[test]
+fn iter_random() {
+    let mut rng = thread_rng();
+    for format in FORMATS {
+        let v: Vec<u64> = (0..5000).map(|_| rng.gen_range(0..1 << 35)).collect();
+        let vb = VByteVector::build(&v, format, 128);
+        assert_eq!(vb.iter().collect::<Vec<u64>>(), v);
+    }
+}
+
+// fn: from_int_vector, to_int_vector
+#[test]
+fn int_vector_round_trip() {
+    let mut rng = thread_rng();
+    for format in FORMATS {
+        let mut iv = IntVector::new(300, 17);
+        for i in 0..iv.len() { iv.set(i, rng.gen_range(0..1 << 17)); }
+        iv.set(0, (1 << 17) - 1);
+        let vb = VByteVector::from_int_vector(&iv, format, 64);
+        assert_eq!(vb.to_int_vector(), iv);
+    }
+}
@@ -0,0 +1,349 @@
+use std::vec::Vec;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use crate::bitvectors::{Bitvector, Bit};
+use crate::int_vector::{IntVector, bit_length};
+use crate::rank_support::RankSupport;
+use crate::structure::StructureNode;
+
+#[cfg(test)]
+mod tests;
+
+/// Wavelet matrix over a sequence of integers. Like the wavelet tree, but without nodes: level
+/// `d` is one `Bitvector` of length `n` containing the `d`-th most significant bit of each
+/// value, and the values of the next level are the values with bit 0 followed by the values
+/// with bit 1, both in their current order. `zeros[d]` is the number of 0bits in level `d`.
+///
+/// All queries take O(log σ) time, where `σ` is the size of the alphabet, except `range_top_k`.
+///
+/// ```
+/// use halko_rust::int_vector::IntVector;
+/// use halko_rust::wavelet_matrix::WaveletMatrix;
+///
+/// let a = [3,1,4,1,5,9,2,6,5,3];
+/// let iv = IntVector::from_slice(&a);
+/// let wm = WaveletMatrix::new(&iv);
+///
+/// assert_eq!(wm.access(5), 9);
+/// assert_eq!(wm.rank(5, 9), 2);
+/// assert_eq!(wm.select(1, 2), 3);
+/// assert_eq!(wm.range_quantile(0, 4, 3), 3);
+/// assert_eq!(wm.range_top_k(0, 9, 2), vec![(1, 2), (3, 2)]);
+/// assert_eq!(wm.range_next_value(0, 4, 2), Some(3));
+/// assert_eq!(wm.range_freq(0, 9, 2, 5), 6);
+/// ```
+pub struct WaveletMatrix {
+    levels: Vec<RankSupport>,
+    zeros: Vec<usize>,
+    l: usize, // number of levels, size of max value in bits
+    n: usize,
+}
+
+impl WaveletMatrix {
+    /// Builds wavelet matrix of the values in `iv`. The number of levels is the number of bits
+    /// of the largest value.
+    pub fn new(iv: &IntVector) -> WaveletMatrix {
+        let n = iv.len();
+        let mut cur: Vec<u64> = iv.to_vec();
+        let max = cur.iter().max().copied().unwrap_or(0);
+        let l = bit_length(max);
+
+        let mut levels = Vec::with_capacity(l);
+        let mut zeros = Vec::with_capacity(l);
+        for d in 0..l {
+            let bit = l-1-d;
+            let mut bv = Bitvector::build_empty(n);
+            let mut next: Vec<u64> = Vec::with_capacity(n);
+            let mut ones = Vec::new();
+            for (i, x) in cur.iter().enumerate() {
+                if (x >> bit) & 1 == 1 {
+                    bv.set(i, Bit::ONE);
+                    ones.push(*x);
+                } else {
+                    next.push(*x);
+                }
+            }
+            zeros.push(next.len());
+            next.extend(ones);
+            levels.push(RankSupport::new(bv));
+            cur = next;
+        }
+
+        WaveletMatrix {
+            levels,
+            zeros,
+            l,
+            n,
+        }
+    }
+
+    /// Returns the length of the sequence.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Returns the number of levels, which is the number of bits of the largest value.
+    pub fn num_levels(&self) -> usize {
+        self.l
+    }
+
+    /// Returns the rank support of the `d`-th level.
+    pub fn get_level(&self, d: usize) -> &RankSupport {
+        &self.levels[d]
+    }
+
+    /// Returns the number of 0bits in the `d`-th level.
+    pub fn get_zeros(&self, d: usize) -> usize {
+        self.zeros[d]
+    }
+
+    /// Returns the `i`-th value of the sequence.
+    pub fn access(&self, i: usize) -> u64 {
+        if i >= self.n {
+            panic!("[WaveletMatrix::access], Index out of bounds, i:{}, WaveletMatrix length:{}", i, self.n);
+        }
+        let mut p = i;
+        let mut c = 0;
+        for d in 0..self.l {
+            let bit = self.levels[d].get_bv().get(p) as u64;
+            p = self.next_pos(d, p, bit);
+            c = (c << 1) | bit;
+        }
+        c
+    }
+
+    /// Returns the number of occurrences of `c` in range `[0,i]`.
+    pub fn rank(&self, c: u64, i: usize) -> u64 {
+        if i >= self.n {
+            panic!("[WaveletMatrix::rank], Index out of bounds, i:{}, WaveletMatrix length:{}", i, self.n);
+        }
+        if !self.in_alphabet(c) {
+            return 0;
+        }
+        let (mut s, mut e) = (0, i+1);
+        for d in 0..self.l {
+            let bit = self.bit(c, d);
+            s = self.next_pos(d, s, bit);
+            e = self.next_pos(d, e, bit);
+        }
+        (e-s) as u64
+    }
+
+    /// Returns index of the `k`-th occurrence of `c`.
+    /// Function panics if `k=0` or if `c` occurs less than `k` times.
+    pub fn select(&self, c: u64, k: usize) -> usize {
+        let count = if self.n == 0 || !self.in_alphabet(c) {0} else {self.rank(c, self.n-1)};
+        if k == 0 || k as u64 > count {
+            panic!("[WaveletMatrix::select], Invalid select query, c:{}, k:{}, number of occurrences:{}",
+                   c, k, count);
+        }
+
+        // start of the values c in the last level
+        let mut s = 0;
+        for d in 0..self.l {
+            s = self.next_pos(d, s, self.bit(c, d));
+        }
+
+        let mut p = s + k-1;
+        for d in (0..self.l).rev() {
+            p = if self.bit(c, d) == 1 {
+                self.levels[d].select1(p - self.zeros[d] + 1)
+            } else {
+                self.levels[d].select0(p + 1)
+            };
+        }
+        p
+    }
+
+    /// Returns the `k`-th smallest value in range `[a,b]`, where `k` starts from 1.
+    /// Function panics if `k=0` or `k>b-a+1`.
+    ///
+    /// ```
+    /// use halko_rust::int_vector::IntVector;
+    /// use halko_rust::wavelet_matrix::WaveletMatrix;
+    ///
+    /// let a = [5,1,4,1,3];
+    /// let iv = IntVector::from_slice(&a);
+    /// let wm = WaveletMatrix::new(&iv);
+    ///
+    /// assert_eq!(wm.range_quantile(0, 4, 1), 1);
+    /// assert_eq!(wm.range_quantile(0, 4, 2), 1);
+    /// assert_eq!(wm.range_quantile(0, 4, 5), 5);
+    /// assert_eq!(wm.range_quantile(2, 4, 2), 3);
+    /// ```
+    pub fn range_quantile(&self, a: usize, b: usize, k: usize) -> u64 {
+        self.check_range("range_quantile", a, b);
+        if k == 0 || k > b-a+1 {
+            panic!("[WaveletMatrix::range_quantile], Invalid k, k:{}, range length:{}", k, b-a+1);
+        }
+        let (mut s, mut e, mut k) = (a, b+1, k);
+        let mut c = 0;
+        for d in 0..self.l {
+            let z = (e - s) - (self.ones_before(d, e) - self.ones_before(d, s));
+            let bit = if k <= z {0} else {k -= z; 1};
+            s = self.next_pos(d, s, bit);
+            e = self.next_pos(d, e, bit);
+            c = (c << 1) | bit;
+        }
+        c
+    }
+
+    /// Returns the `k` most frequent values in range `[a,b]` with their frequencies, in
+    /// decreasing order of frequency. Values of equal frequency are in increasing order.
+    /// Nodes of the matrix are expanded in decreasing order of their size, so the search stops
+    /// after the `k`-th leaf.
+    ///
+    /// ```
+    /// use halko_rust::int_vector::IntVector;
+    /// use halko_rust::wavelet_matrix::WaveletMatrix;
+    ///
+    /// let a = [2,7,2,3,7,2,1];
+    /// let iv = IntVector::from_slice(&a);
+    /// let wm = WaveletMatrix::new(&iv);
+    ///
+    /// assert_eq!(wm.range_top_k(0, 6, 2), vec![(2, 3), (7, 2)]);
+    /// assert_eq!(wm.range_top_k(3, 6, 10), vec![(1, 1), (2, 1), (3, 1), (7, 1)]);
+    /// ```
+    pub fn range_top_k(&self, a: usize, b: usize, k: usize) -> Vec<(u64, usize)> {
+        self.check_range("range_top_k", a, b);
+        let mut res = Vec::with_capacity(k);
+
+        // (size, smallest value of the node, depth, node range [s,e))
+        let mut heap = BinaryHeap::new();
+        heap.push((b+1-a, Reverse(0), Reverse(0), a, b+1));
+        while let Some((size, Reverse(lo), Reverse(d), s, e)) = heap.pop() {
+            if res.len() == k {
+                break;
+            }
+            if d == self.l {
+                res.push((lo, size));
+                continue;
+            }
+            let bit_value = 1 << (self.l-1-d);
+            for bit in 0..2 {
+                let s2 = self.next_pos(d, s, bit);
+                let e2 = self.next_pos(d, e, bit);
+                if s2 < e2 {
+                    heap.push((e2-s2, Reverse(lo | (bit*bit_value)), Reverse(d+1), s2, e2));
+                }
+            }
+        }
+        res
+    }
+
+    /// Returns the smallest value `y>=x` in range `[a,b]`, or `None` if all values are smaller than `x`.
+    ///
+    /// ```
+    /// use halko_rust::int_vector::IntVector;
+    /// use halko_rust::wavelet_matrix::WaveletMatrix;
+    ///
+    /// let a = [5,1,4,1,3];
+    /// let iv = IntVector::from_slice(&a);
+    /// let wm = WaveletMatrix::new(&iv);
+    ///
+    /// assert_eq!(wm.range_next_value(0, 4, 2), Some(3));
+    /// assert_eq!(wm.range_next_value(0, 4, 5), Some(5));
+    /// assert_eq!(wm.range_next_value(1, 3, 5), None);
+    /// ```
+    pub fn range_next_value(&self, a: usize, b: usize, x: u64) -> Option<u64> {
+        self.check_range("range_next_value", a, b);
+        let smaller = self.count_less(a, b+1, x);
+        if smaller == b-a+1 {
+            return None;
+        }
+        Some(self.range_quantile(a, b, smaller+1))
+    }
+
+    /// Returns the number of values `x` with `lo <= x <= hi` in range `[a,b]`.
+    ///
+    /// ```
+    /// use halko_rust::int_vector::IntVector;
+    /// use halko_rust::wavelet_matrix::WaveletMatrix;
+    ///
+    /// let a = [5,1,4,1,3];
+    /// let iv = IntVector::from_slice(&a);
+    /// let wm = WaveletMatrix::new(&iv);
+    ///
+    /// assert_eq!(wm.range_freq(0, 4, 1, 4), 4);
+    /// assert_eq!(wm.range_freq(2, 4, 0, u64::MAX), 3);
+    /// assert_eq!(wm.range_freq(0, 4, 4, 1), 0);
+    /// ```
+    pub fn range_freq(&self, a: usize, b: usize, lo: u64, hi: u64) -> usize {
+        self.check_range("range_freq", a, b);
+        if lo > hi {
+            return 0;
+        }
+        let upto_hi = match hi.checked_add(1) {
+            Some(x) => self.count_less(a, b+1, x),
+            None => b-a+1,
+        };
+        upto_hi - self.count_less(a, b+1, lo)
+    }
+
+    /// Returns the size of the matrix in bytes.
+    pub fn size_in_bytes(&self) -> usize {
+        self.levels.iter().map(|rs| rs.size_in_bytes()).sum::<usize>() + 8*self.zeros.len() + 16
+    }
+
+    /// Returns the space breakdown of the matrix, one node per level.
+    pub fn structure_tree(&self) -> StructureNode {
+        let mut node = StructureNode::new("WaveletMatrix", self.size_in_bytes())
+            .with_field("levels", self.l as u64)
+            .with_field("n", self.n as u64);
+        for (d, rs) in self.levels.iter().enumerate() {
+            node = node.with_child(rs.structure_tree().with_name(&format!("level{}", d))
+                                   .with_field("zeros", self.zeros[d] as u64));
+        }
+        node
+    }
+
+    // Number of values smaller than x in range [s,e) of the first level.
+    fn count_less(&self, s: usize, e: usize, x: u64) -> usize {
+        if !self.in_alphabet(x) {
+            return e-s;
+        }
+        let (mut s, mut e) = (s, e);
+        let mut res = 0;
+        for d in 0..self.l {
+            let bit = self.bit(x, d);
+            if bit == 1 {
+                res += (e - s) - (self.ones_before(d, e) - self.ones_before(d, s));
+            }
+            s = self.next_pos(d, s, bit);
+            e = self.next_pos(d, e, bit);
+        }
+        res
+    }
+
+    fn check_range(&self, f: &str, a: usize, b: usize) {
+        if a > b || b >= self.n {
+            panic!("[WaveletMatrix::{}], Invalid range, a:{}, b:{}, WaveletMatrix length:{}",
+                   f, a, b, self.n);
+        }
+    }
+
+    fn in_alphabet(&self, c: u64) -> bool {
+        self.l == 64 || c >> self.l == 0
+    }
+
+    // Bit of c in level d.
+    fn bit(&self, c: u64, d: usize) -> u64 {
+        (c >> (self.l-1-d)) & 1
+    }
+
+    // Number of 1s in range [0,p) of level d.
+    fn ones_before(&self, d: usize, p: usize) -> usize {
+        if p == 0 {0} else {self.levels[d].rank1(p-1) as usize}
+    }
+
+    // Position in level d+1 of the position p of level d, when the bit at p is `bit`.
+    // Works also for p=n, giving the end of the values with the bit.
+    fn next_pos(&self, d: usize, p: usize, bit: u64) -> usize {
+        let ones = self.ones_before(d, p);
+        if bit == 0 {p - ones} else {self.zeros[d] + ones}
+    }
+}
@@ -0,0 +1,167 @@
+use std::vec::Vec;
+use rand::{Rng, thread_rng};
+
+use crate::wavelet_matrix::WaveletMatrix;
+use crate::int_vector::IntVector;
+
+fn random_values(n: usize, sigma: u64) -> Vec<u64> {
+    let mut rng = thread_rng();
+    (0..n).map(|_| rng.gen_range(0..sigma)).collect()
+}
+
+fn random_range(n: usize) -> (usize, usize) {
+    let mut rng = thread_rng();
+    let x = rng.gen_range(0..n);
+    let y = rng.gen_range(0..n);
+    (x.min(y), x.max(y))
+}
+
+// fn: new
+#[test]
+fn wm_new_zeros() {
+    let wm = WaveletMatrix::new(&IntVector::from_slice(&[4,1,6,3]));
+    assert_eq!(wm.num_levels(), 3);
+    // level 0 bits: 1,0,1,0
+    assert_eq!(wm.get_zeros(0), 2);
+    // level 1 values [1,3,4,6], bits: 0,1,0,1
+    assert_eq!(wm.get_zeros(1), 2);
+    // level 2 values [1,4,3,6], bits: 1,0,1,0
+    assert_eq!(wm.get_zeros(2), 2);
+
+    let wm = WaveletMatrix::new(&IntVector::new(0, 3));
+    assert!(wm.is_empty());
+    assert_eq!(wm.num_levels(), 1);
+}
+
+// fn: access
+#[test]
+fn wm_access_random() {
+    for sigma in [2, 5, 256, 100_000] {
+        let a = random_values(2000, sigma);
+        let wm = WaveletMatrix::new(&IntVector::from_slice(&a));
+        for (i, x) in a.iter().enumerate() {
+            assert_eq!(wm.access(i), *x);
+        }
+    }
+}
+
+// fn: access, select
+#[test]
+fn wm_full_width() {
+    let a = vec![u64::MAX, 0, 1 << 63, 12345, u64::MAX];
+    let wm = WaveletMatrix::new(&IntVector::from_slice(&a));
+    for (i, x) in a.iter().enumerate() {
+        assert_eq!(wm.access(i), *x);
+    }
+    assert_eq!(wm.select(u64::MAX, 2), 4);
+    assert_eq!(wm.range_freq(0, 4, 1 << 63, u64::MAX), 3);
+    assert_eq!(wm.range_quantile(0, 4, 3), 1 << 63);
+    assert_eq!(wm.range_next_value(0, 4, 12346), Some(1 << 63));
+}
+
+// fn: rank, select
+#[test]
+fn wm_rank_select_random() {
+    for sigma in [2, 7, 64] {
+        let a = random_values(1000, sigma);
+        let wm = WaveletMatrix::new(&IntVector::from_slice(&a));
+        for c in 0..sigma+2 {
+            let mut count = 0;
+            for (i, x) in a.iter().enumerate() {
+                if *x == c {
+                    count += 1;
+                    assert_eq!(wm.select(c, count), i);
+                }
+                assert_eq!(wm.rank(c, i), count as u64);
+            }
+        }
+    }
+}
+
+// fn: select
+#[test]
+#[should_panic]
+fn wm_select_missing_symbol() {
+    let wm = WaveletMatrix::new(&IntVector::from_slice(&[1,2,3]));
+    wm.select(0, 1);
+}
+
+// fn: range_quantile
+#[test]
+fn wm_range_quantile_random() {
+    let a = random_values(300, 1000);
+    let wm = WaveletMatrix::new(&IntVector::from_slice(&a));
+    for _ in 0..500 {
+        let (s, e) = random_range(a.len());
+        let mut sorted = a[s..=e].to_vec();
+        sorted.sort();
+        for (k, x) in sorted.iter().enumerate() {
+            assert_eq!(wm.range_quantile(s, e, k+1), *x);
+        }
+    }
+}
+
+// fn: range_quantile
+#[test]
+#[should_panic]
+fn wm_range_quantile_invalid_k() {
+    let wm = WaveletMatrix::new(&IntVector::from_slice(&[1,2,3]));
+    wm.range_quantile(1, 2, 3);
+}
+
+// fn: range_top_k
+#[test]
+fn wm_range_top_k_random() {
+    let a = random_values(500, 20);
+    let wm = WaveletMatrix::new(&IntVector::from_slice(&a));
+    for _ in 0..300 {
+        let (s, e) = random_range(a.len());
+        let mut freqs: Vec<(u64, usize)> = (0..20)
+            .map(|c| (c, a[s..=e].iter().filter(|x| **x == c).count()))
+            .filter(|(_, f)| *f > 0)
+            .collect();
+        freqs.sort_by(|x, y| y.1.cmp(&x.1).then(x.0.cmp(&y.0)));
+        for k in [0, 1, 3, 25] {
+            let expected: Vec<(u64, usize)> = freqs.iter().take(k).copied().collect();
+            assert_eq!(wm.range_top_k(s, e, k), expected);
+        }
+    }
+}
+
+// fn: range_next_value
+#[test]
+fn wm_range_next_value_random() {
+    let mut rng = thread_rng();
+    let a = random_values(300, 500);
+    let wm = WaveletMatrix::new(&IntVector::from_slice(&a));
+    for _ in 0..2000 {
+        let (s, e) = random_range(a.len());
+        let x = rng.gen_range(0..600);
+        let expected = a[s..=e].iter().filter(|y| **y >= x).min().copied();
+        assert_eq!(wm.range_next_value(s, e, x), expected);
+    }
+}
+
+// fn: range_freq
+#[test]
+fn wm_range_freq_random() {
+    let mut rng = thread_rng();
+    let a = random_values(300, 50);
+    let wm = WaveletMatrix::new(&IntVector::from_slice(&a));
+    for _ in 0..2000 {
+        let (s, e) = random_range(a.len());
+        let u = rng.gen_range(0..70);
+        let v = rng.gen_range(0..70);
+        let (lo, hi) = (u.min(v), u.max(v));
+        let expected = a[s..=e].iter().filter(|x| lo <= **x && **x <= hi).count();
+        assert_eq!(wm.range_freq(s, e, lo, hi), expected);
+    }
+}
+
+// fn: range_freq
+#[test]
+#[should_panic]
+fn wm_range_freq_invalid_range() {
+    let wm = WaveletMatrix::new(&IntVector::from_slice(&[1,2,3]));
+    wm.range_freq(2, 1, 0, 3);
+}
@@ -0,0 +1,287 @@
+use std::vec::Vec;
+use crate::bitvectors::{Bitvector, Bit};
+use crate::int_vector::{IntVector, bit_length};
+use crate::rank_support::RankSupport;
+use crate::structure::StructureNode;
+
+#[cfg(test)]
+mod tests;
+
+/// Balanced wavelet tree over a sequence of integers. Supports access, rank and select of
+/// symbols and counting of values in a range in O(log σ) time, where `σ` is the size of the
+/// alphabet.
+///
+/// The tree is stored level by level: level `d` is one `Bitvector` of length `n` containing
+/// the `d`-th most significant bit of each value, with values ordered stably by their first
+/// `d` bits. Each node of the tree is a range of its level, and child nodes are found by rank
+/// queries.
+///
+/// ```
+/// use halko_rust::int_vector::IntVector;
+/// use halko_rust::wavelet_tree::WaveletTree;
+///
+/// let a = [3,1,4,1,5,2,6,5];
+/// let iv = IntVector::from_slice(&a);
+/// let wt = WaveletTree::new(&iv);
+///
+/// assert_eq!(wt.access(4), 5);
+/// assert_eq!(wt.rank(1, 3), 2);
+/// assert_eq!(wt.select(5, 2), 7);
+/// assert_eq!(wt.range_count(0, 7, 2, 4), 3);
+/// ```
+pub struct WaveletTree {
+    levels: Vec<RankSupport>,
+    l: usize, // number of levels, size of max value in bits
+    n: usize,
+}
+
+impl WaveletTree {
+    /// Builds wavelet tree of the values in `iv`. The height of the tree is the number of bits
+    /// of the largest value.
+    pub fn new(iv: &IntVector) -> WaveletTree {
+        let n = iv.len();
+        let mut cur: Vec<u64> = iv.to_vec();
+        let max = cur.iter().max().copied().unwrap_or(0);
+        let l = bit_length(max);
+
+        let mut levels = Vec::with_capacity(l);
+        for d in 0..l {
+            let bit = l-1-d;
+            let mut bv = Bitvector::build_empty(n);
+            for (i, x) in cur.iter().enumerate() {
+                if (x >> bit) & 1 == 1 {
+                    bv.set(i, Bit::ONE);
+                }
+            }
+            levels.push(RankSupport::new(bv));
+
+            // stable partition of each node by the current bit
+            let mut next = Vec::with_capacity(n);
+            let mut start = 0;
+            while start < n {
+                let prefix = cur[start].checked_shr(bit as u32 + 1).unwrap_or(0);
+                let mut stop = start;
+                while stop < n && cur[stop].checked_shr(bit as u32 + 1).unwrap_or(0) == prefix {
+                    stop += 1;
+                }
+                next.extend(cur[start..stop].iter().filter(|x| (*x >> bit) & 1 == 0));
+                next.extend(cur[start..stop].iter().filter(|x| (*x >> bit) & 1 == 1));
+                start = stop;
+            }
+            cur = next;
+        }
+
+        WaveletTree {
+            levels,
+            l,
+            n,
+        }
+    }
+
+    /// Returns the length of the sequence.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Returns the number of levels in the tree, which is the number of bits of the largest value.
+    pub fn num_levels(&self) -> usize {
+        self.l
+    }
+
+    /// Returns the rank support of the `d`-th level.
+    pub fn get_level(&self, d: usize) -> &RankSupport {
+        &self.levels[d]
+    }
+
+    /// Returns the `i`-th value of the sequence.
+    ///
+    /// ```
+    /// use halko_rust::int_vector::IntVector;
+    /// use halko_rust::wavelet_tree::WaveletTree;
+    ///
+    /// let iv = IntVector::from_slice(&[9,0,15]);
+    /// let wt = WaveletTree::new(&iv);
+    ///
+    /// assert_eq!(wt.access(0), 9);
+    /// assert_eq!(wt.access(1), 0);
+    /// assert_eq!(wt.access(2), 15);
+    /// ```
+    pub fn access(&self, i: usize) -> u64 {
+        if i >= self.n {
+            panic!("[WaveletTree::access], Index out of bounds, i:{}, WaveletTree length:{}", i, self.n);
+        }
+        let (mut s, mut e, mut p) = (0, self.n, i);
+        let mut c = 0;
+        for d in 0..self.l {
+            let bit = self.levels[d].get_bv().get(s+p) as u64;
+            (s, e, p) = self.child(d, s, e, p, bit);
+            c = (c << 1) | bit;
+        }
+        c
+    }
+
+    /// Returns the number of occurrences of `c` in range `[0,i]`.
+    ///
+    /// ```
+    /// use halko_rust::int_vector::IntVector;
+    /// use halko_rust::wavelet_tree::WaveletTree;
+    ///
+    /// let iv = IntVector::from_slice(&[2,0,2,0]);
+    /// let wt = WaveletTree::new(&iv);
+    ///
+    /// assert_eq!(wt.rank(2, 0), 1);
+    /// assert_eq!(wt.rank(2, 3), 2);
+    /// assert_eq!(wt.rank(0, 3), 2);
+    /// assert_eq!(wt.rank(7, 3), 0);
+    /// ```
+    pub fn rank(&self, c: u64, i: usize) -> u64 {
+        if i >= self.n {
+            panic!("[WaveletTree::rank], Index out of bounds, i:{}, WaveletTree length:{}", i, self.n);
+        }
+        if self.l < 64 && c >> self.l != 0 {
+            return 0;
+        }
+        let (mut s, mut e, mut p) = (0, self.n, i+1);
+        for d in 0..self.l {
+            (s, e, p) = self.child(d, s, e, p, (c >> (self.l-1-d)) & 1);
+        }
+        p as u64
+    }
+
+    /// Returns index of the `k`-th occurrence of `c`.
+    /// Function panics if `k=0` or if `c` occurs less than `k` times.
+    ///
+    /// ```
+    /// use std::panic;
+    /// use halko_rust::int_vector::IntVector;
+    /// use halko_rust::wavelet_tree::WaveletTree;
+    ///
+    /// let iv = IntVector::from_slice(&[2,0,2,0]);
+    /// let wt = WaveletTree::new(&iv);
+    ///
+    /// assert_eq!(wt.select(2, 1), 0);
+    /// assert_eq!(wt.select(2, 2), 2);
+    /// assert_eq!(wt.select(0, 2), 3);
+    ///
+    /// let panic_result = panic::catch_unwind(|| {
+    ///     wt.select(2, 3)
+    /// });
+    /// assert!(panic_result.is_err());
+    /// ```
+    pub fn select(&self, c: u64, k: usize) -> usize {
+        let count = if self.n == 0 || (self.l < 64 && c >> self.l != 0) {0} else {self.rank(c, self.n-1)};
+        if k == 0 || k as u64 > count {
+            panic!("[WaveletTree::select], Invalid select query, c:{}, k:{}, number of occurrences:{}",
+                   c, k, count);
+        }
+
+        // node starts on the path to the leaf of c
+        let mut starts = Vec::with_capacity(self.l);
+        let (mut s, mut e) = (0, self.n);
+        for d in 0..self.l {
+            starts.push(s);
+            (s, e, _) = self.child(d, s, e, 0, (c >> (self.l-1-d)) & 1);
+        }
+
+        let mut p = k-1;
+        for d in (0..self.l).rev() {
+            let s = starts[d];
+            let before = self.ones_before(d, s);
+            p = if (c >> (self.l-1-d)) & 1 == 1 {
+                self.levels[d].select1(before + p + 1)
+            } else {
+                self.levels[d].select0(s - before + p + 1)
+            } - s;
+        }
+        p
+    }
+
+    /// Returns the number of values `x` with `lo <= x <= hi` in range `[a,b]`.
+    ///
+    /// ```
+    /// use halko_rust::int_vector::IntVector;
+    /// use halko_rust::wavelet_tree::WaveletTree;
+    ///
+    /// let a = [3,1,4,1,5,2,6,5];
+    /// let iv = IntVector::from_slice(&a);
+    /// let wt = WaveletTree::new(&iv);
+    ///
+    /// assert_eq!(wt.range_count(1, 4, 1, 4), 3);
+    /// assert_eq!(wt.range_count(0, 7, 5, 100), 3);
+    /// assert_eq!(wt.range_count(0, 7, 4, 3), 0);
+    /// ```
+    pub fn range_count(&self, a: usize, b: usize, lo: u64, hi: u64) -> usize {
+        if a > b || b >= self.n {
+            panic!("[WaveletTree::range_count], Invalid range, a:{}, b:{}, WaveletTree length:{}",
+                   a, b, self.n);
+        }
+        if lo > hi {
+            return 0;
+        }
+        self.range_count_node(0, (0, self.n), (a, b+1), 0, lo, hi)
+    }
+
+    // Counts values in `[lo,hi]` in node `[s,e)` of level `d` between node positions `[a,b)`.
+    // All values in the node start with the bits of `prefix`.
+    fn range_count_node(&self, d: usize, (s, e): (usize, usize), (a, b): (usize, usize), prefix: u64,
+                        lo: u64, hi: u64) -> usize {
+        if a >= b {
+            return 0;
+        }
+        let shift = self.l - d;
+        let node_lo = if shift == 64 {0} else {prefix << shift};
+        let node_hi = if shift == 64 {u64::MAX} else {node_lo | !(u64::MAX << shift)};
+        if node_hi < lo || hi < node_lo {
+            return 0;
+        }
+        if lo <= node_lo && node_hi <= hi {
+            return b-a;
+        }
+
+        let (s0, e0, a0) = self.child(d, s, e, a, 0);
+        let (_, _, b0) = self.child(d, s, e, b, 0);
+        let (s1, e1, a1) = self.child(d, s, e, a, 1);
+        let (_, _, b1) = self.child(d, s, e, b, 1);
+        self.range_count_node(d+1, (s0, e0), (a0, b0), prefix << 1, lo, hi)
+            + self.range_count_node(d+1, (s1, e1), (a1, b1), (prefix << 1) | 1, lo, hi)
+    }
+
+    /// Returns the size of the tree in bytes.
+    pub fn size_in_bytes(&self) -> usize {
+        self.levels.iter().map(|rs| rs.size_in_bytes()).sum::<usize>() + 16
+    }
+
+    /// Returns the space breakdown of the tree, one node per level.
+    pub fn structure_tree(&self) -> StructureNode {
+        let mut node = StructureNode::new("WaveletTree", self.size_in_bytes())
+            .with_field("levels", self.l as u64)
+            .with_field("n", self.n as u64);
+        for (d, rs) in self.levels.iter().enumerate() {
+            node = node.with_child(rs.structure_tree().with_name(&format!("level{}", d)));
+        }
+        node
+    }
+
+    // Number of 1s in range [0,pos) of level d.
+    fn ones_before(&self, d: usize, pos: usize) -> usize {
+        if pos == 0 {0} else {self.levels[d].rank1(pos-1) as usize}
+    }
+
+    // Moves from node `[s,e)` of level `d` into its child `bit`. Returns the child node and
+    // the number of values with the bit among the first `p` values of the node.
+    fn child(&self, d: usize, s: usize, e: usize, p: usize, bit: u64) -> (usize, usize, usize) {
+        let ones_s = self.ones_before(d, s);
+        let ones_e = self.ones_before(d, e);
+        let zeros = (e-s) - (ones_e-ones_s);
+        let ones_p = self.ones_before(d, s+p) - ones_s;
+        if bit == 0 {
+            (s, s+zeros, p-ones_p)
+        } else {
+            (s+zeros, e, ones_p)
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use std::vec::Vec;
+use rand::{Rng, thread_rng};
+
+use crate::wavelet_tree::WaveletTree;
+use crate::int_vector::IntVector;
+
+fn random_values(n: usize, sigma: u64) -> Vec<u64> {
+    let mut rng = thread_rng();
+    (0..n).map(|_| rng.gen_range(0..sigma)).collect()
+}
+
+// fn: new
+#[test]
+fn wt_new_levels() {
+    let wt = WaveletTree::new(&IntVector::from_slice(&[0,7,3]));
+    assert_eq!(wt.num_levels(), 3);
+    assert_eq!(wt.len(), 3);
+
+    let wt = WaveletTree::new(&IntVector::from_slice(&[0,0,0]));
+    assert_eq!(wt.num_levels(), 1);
+    assert_eq!(wt.access(2), 0);
+
+    let wt = WaveletTree::new(&IntVector::new(0, 5));
+    assert!(wt.is_empty());
+}
+
+// fn: access
+#[test]
+fn wt_access_random() {
+    for sigma in [2, 5, 256, 1000] {
+        let a = random_values(2000, sigma);
+        let wt = WaveletTree::new(&IntVector::from_slice(&a));
+        for (i, x) in a.iter().enumerate() {
+            assert_eq!(wt.access(i), *x);
+        }
+    }
+}
+
+// fn: access
+#[test]
+fn wt_access_full_width() {
+    let a = vec![u64::MAX, 0, 1 << 63, 12345];
+    let wt = WaveletTree::new(&IntVector::from_slice(&a));
+    assert_eq!(wt.num_levels(), 64);
+    for (i, x) in a.iter().enumerate() {
+        assert_eq!(wt.access(i), *x);
+        assert_eq!(wt.select(*x, 1), i);
+    }
+}
+
+// fn: access
+#[test]
+#[should_panic]
+fn wt_access_out_of_bounds() {
+    let wt = WaveletTree::new(&IntVector::from_slice(&[1,2,3]));
+    wt.access(3);
+}
+
+// fn: rank
+#[test]
+fn wt_rank_random() {
+    for sigma in [2, 7, 64] {
+        let a = random_values(1000, sigma);
+        let wt = WaveletTree::new(&IntVector::from_slice(&a));
+        for c in 0..sigma+2 {
+            let mut count = 0;
+            for (i, x) in a.iter().enumerate() {
+                if *x == c {
+                    count += 1;
+                }
+                assert_eq!(wt.rank(c, i), count);
+            }
+        }
+    }
+}
+
+// fn: select
+#[test]
+fn wt_select_random() {
+    for sigma in [2, 7, 64] {
+        let a = random_values(1000, sigma);
+        let wt = WaveletTree::new(&IntVector::from_slice(&a));
+        let mut counts = vec![0; sigma as usize];
+        for (i, x) in a.iter().enumerate() {
+            counts[*x as usize] += 1;
+            assert_eq!(wt.select(*x, counts[*x as usize]), i);
+        }
+    }
+}
+
+// fn: select
+#[test]
+#[should_panic]
+fn wt_select_missing_symbol() {
+    let wt = WaveletTree::new(&IntVector::from_slice(&[1,2,3]));
+    wt.select(0, 1);
+}
+
+// fn: range_count
+#[test]
+fn wt_range_count_random() {
+    let mut rng = thread_rng();
+    let a = random_values(300, 50);
+    let wt = WaveletTree::new(&IntVector::from_slice(&a));
+    for _ in 0..2000 {
+        let x = rng.gen_range(0..a.len());
+        let y = rng.gen_range(0..a.len());
+        let (s, e) = (x.min(y), x.max(y));
+        let u = rng.gen_range(0..60);
+        let v = rng.gen_range(0..60);
+        let (lo, hi) = (u.min(v), u.max(v));
+
+        let expected = a[s..=e].iter().filter(|x| lo <= **x && **x <= hi).count();
+        assert_eq!(wt.range_count(s, e, lo, hi), expected);
+    }
+}
+
+// fn: range_count
+#[test]
+#[should_panic]
+fn wt_range_count_invalid_range() {
+    let wt = WaveletTree::new(&IntVector::from_slice(&[1,2,3]));
+    wt.range_count(1, 3, 0, 3);
+}